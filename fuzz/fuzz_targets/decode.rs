@@ -0,0 +1,12 @@
+#![no_main]
+
+use base64::Base64;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for engine in [Base64::standard(), Base64::url_safe()] {
+        if let Ok(decoded) = engine.decode(data) {
+            assert!(decoded.len() <= data.len());
+        }
+    }
+});
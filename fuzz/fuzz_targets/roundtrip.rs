@@ -0,0 +1,14 @@
+#![no_main]
+
+use base64::Base64;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for engine in [Base64::standard(), Base64::url_safe(), Base64::url_safe_no_pad()] {
+        let encoded = engine.encode(data);
+        assert_eq!(encoded.len(), engine.encoded_len(data.len()));
+
+        let decoded = engine.decode(&encoded).expect("encoded output should decode");
+        assert_eq!(decoded, data);
+    }
+});
@@ -0,0 +1,102 @@
+use crate::{AlphabetError, Base64};
+
+/// The 64 ASCII symbols of an engine, each mapped to the 6-bit value of its position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: [u8; 64],
+}
+
+impl Alphabet {
+    pub const fn standard() -> Self {
+        Self {
+            symbols: Base64::ALPHABET_STANDARD,
+        }
+    }
+
+    pub const fn url_safe() -> Self {
+        Self {
+            symbols: Base64::ALPHABET_URL_SAFE,
+        }
+    }
+
+    // `symbols` must already be 64 unique ASCII bytes
+    pub(crate) const fn from_array(symbols: [u8; 64]) -> Self {
+        Self { symbols }
+    }
+
+    /// Builds an alphabet out of exactly 64 unique ASCII `symbols`.
+    pub fn new(symbols: &[u8]) -> Result<Self, AlphabetError> {
+        let symbols = <[u8; 64]>::try_from(symbols)
+            .map_err(|_| AlphabetError::InvalidLength { len: symbols.len() })?;
+
+        for (i, byte) in symbols.iter().enumerate() {
+            if !byte.is_ascii() {
+                return Err(AlphabetError::NonAsciiSymbol { byte: *byte });
+            }
+            if symbols[..i].contains(byte) {
+                return Err(AlphabetError::DuplicateSymbol { byte: *byte });
+            }
+        }
+
+        Ok(Self { symbols })
+    }
+
+    /// Replaces the symbol `from` with `to`, keeping its 6-bit value, e.g. to build the
+    /// standard alphabet with `~` instead of `/`.
+    pub fn swap(mut self, from: u8, to: u8) -> Result<Self, AlphabetError> {
+        let idx = self
+            .symbols
+            .iter()
+            .position(|byte| *byte == from)
+            .ok_or(AlphabetError::MissingSymbol { byte: from })?;
+
+        if !to.is_ascii() {
+            return Err(AlphabetError::NonAsciiSymbol { byte: to });
+        }
+        if from != to && self.symbols.contains(&to) {
+            return Err(AlphabetError::DuplicateSymbol { byte: to });
+        }
+
+        self.symbols[idx] = to;
+        Ok(self)
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 64] {
+        &self.symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64Engine;
+
+    #[test]
+    fn alphabet_swap_works() {
+        let alphabet = Alphabet::standard()
+            .swap(b'/', b'~')
+            .expect("should swap symbol");
+        let engine = Base64::standard()
+            .with_alphabet(alphabet)
+            .expect("should be a valid alphabet");
+
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+        let encoded = engine.encode(input);
+        assert_eq!(encoded, "+~+~~g==");
+        assert_eq!(engine.decode(&encoded), Ok(input.to_vec()));
+    }
+
+    #[test]
+    fn alphabet_swap_rejects_invalid_symbols() {
+        let err = Alphabet::standard().swap(b'/', b'+').unwrap_err();
+        assert_eq!(err, AlphabetError::DuplicateSymbol { byte: b'+' });
+
+        let err = Alphabet::standard().swap(b'-', b'~').unwrap_err();
+        assert_eq!(err, AlphabetError::MissingSymbol { byte: b'-' });
+
+        let alphabet = Alphabet::standard().swap(b'/', b'=').expect("should swap");
+        let err = Base64::standard().with_alphabet(alphabet).unwrap_err();
+        assert_eq!(err, AlphabetError::PaddingInAlphabet { byte: b'=' });
+        assert!(Base64Engine::new(alphabet.as_bytes(), None).is_ok());
+    }
+}
@@ -0,0 +1,64 @@
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, DecodeError};
+
+/// Output of [`Base64Engine::decode_classified`], classified by its length so that short
+/// tokens are decoded without allocating.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedChunk {
+    Empty,
+    OneByte([u8; 1]),
+    TwoBytes([u8; 2]),
+    ThreeBytes([u8; 3]),
+    /// Output of input longer than a single window, which may still be less than 4 bytes long.
+    Many(Vec<u8>),
+}
+
+impl Base64Engine {
+    /// Decodes `encoded`, returning the output of a single window of up to 4 chars on the
+    /// stack, classified by its length, and falling back to a `Vec` for longer input.
+    pub fn decode_classified(
+        &self,
+        encoded: impl AsRef<[u8]>,
+    ) -> Result<DecodedChunk, DecodeError> {
+        let encoded = encoded.as_ref();
+        if Self::trim_newline(encoded).len() > 4 {
+            return self.decode(encoded).map(DecodedChunk::Many);
+        }
+
+        let ([a, b, c], len) = self.decode_to_array::<3>(encoded)?;
+        Ok(match len {
+            0 => DecodedChunk::Empty,
+            1 => DecodedChunk::OneByte([a]),
+            2 => DecodedChunk::TwoBytes([a, b]),
+            _ => DecodedChunk::ThreeBytes([a, b, c]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn decode_classified_works() {
+        let engine = Base64::standard();
+        let config = [
+            ("", DecodedChunk::Empty),
+            ("bA==", DecodedChunk::OneByte(*b"l")),
+            ("bGk=", DecodedChunk::TwoBytes(*b"li")),
+            ("bGln\n", DecodedChunk::ThreeBytes(*b"lig")),
+            ("bGlnaA==", DecodedChunk::Many(b"ligh".to_vec())),
+        ];
+
+        for (input, output) in config {
+            assert_eq!(engine.decode_classified(input), Ok(output));
+        }
+
+        assert_eq!(
+            engine.decode_classified("bA="),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Base64Engine;
+
+impl Base64Engine {
+    /// Returns whether the encodings `a` and `b` are equal once their trailing padding is
+    /// removed, without decoding them. Engines without padding strip `=`.
+    pub fn eq_ignore_padding(&self, a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+        self.strip_padding(a.as_ref()) == self.strip_padding(b.as_ref())
+    }
+
+    /// Same as `eq_ignore_padding`, but also treats the standard `+` and `/` symbols as equal to
+    /// their url-safe `-` and `_` counterparts.
+    pub fn eq_ignore_variant(&self, a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+        let normalize = |byte: &u8| match byte {
+            b'-' => b'+',
+            b'_' => b'/',
+            byte => *byte,
+        };
+
+        let a = self.strip_padding(a.as_ref()).iter().map(normalize);
+        let b = self.strip_padding(b.as_ref()).iter().map(normalize);
+        a.eq(b)
+    }
+
+    /// Returns whether everything this engine decodes was producible by `encoder`, i.e. both
+    /// map symbols to the same values and agree on their padding, e.g. to catch an url-safe
+    /// encoder paired with a standard decoder at startup.
+    ///
+    /// Flags are not compared, the output of any engine being canonical. Padding only marks the
+    /// end of the encoded data, so it is compared only when both engines pad, with engines that
+    /// differ just by whether they pad being compatible.
+    pub fn is_decode_compatible_with(&self, encoder: &Base64Engine) -> bool {
+        let padding = match (self.padding, encoder.padding) {
+            (Some(decoder), Some(encoder)) => decoder == encoder,
+            _ => true,
+        };
+
+        self.alphabet == encoder.alphabet && padding
+    }
+
+    fn strip_padding<'a>(&self, encoded: &'a [u8]) -> &'a [u8] {
+        let padding = self.padding.unwrap_or(b'=');
+        let len = encoded
+            .iter()
+            .rposition(|byte| *byte != padding)
+            .map_or(0, |idx| idx + 1);
+
+        &encoded[..len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Base64;
+
+    #[test]
+    fn eq_ignore_padding_works() {
+        let engine = Base64::standard();
+
+        assert!(engine.eq_ignore_padding("QQ==", "QQ"));
+        assert!(engine.eq_ignore_padding("QUI=", "QUI="));
+        assert!(!engine.eq_ignore_padding("QQ==", "QR"));
+        assert!(!engine.eq_ignore_padding("+/8=", "-_8"));
+        assert!(Base64::url_safe_no_pad().eq_ignore_padding("QQ", "QQ=="));
+    }
+
+    #[test]
+    fn eq_ignore_variant_works() {
+        let engine = Base64::standard();
+
+        assert!(engine.eq_ignore_variant("+/8=", "-_8"));
+        assert!(!engine.eq_ignore_variant("+/8=", "-_9"));
+    }
+
+    #[test]
+    fn is_decode_compatible_with_works() {
+        let standard = Base64::standard();
+        assert!(!standard.is_decode_compatible_with(&Base64::url_safe()));
+        assert!(!Base64::url_safe().is_decode_compatible_with(&standard));
+        assert!(!standard.is_decode_compatible_with(&Base64::url_safe_no_pad()));
+        assert!(!Base64::imap().is_decode_compatible_with(&standard));
+
+        let strict = Base64::standard().require_canonical(true);
+        assert!(strict.is_decode_compatible_with(&standard));
+        assert!(standard.is_decode_compatible_with(&strict));
+
+        // engines differing only by whether they pad decode the same values
+        let unpadded = Base64::standard().without_padding();
+        assert!(standard.is_decode_compatible_with(&unpadded));
+        assert!(unpadded.is_decode_compatible_with(&standard));
+
+        let dotted = Base64::standard()
+            .with_padding(Some('.'))
+            .expect("valid padding");
+        assert!(!standard.is_decode_compatible_with(&dotted));
+        assert!(!dotted.is_decode_compatible_with(&standard));
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::string::String;
+
+use crate::{AlphabetError, Base64, Base64Engine};
+
+/// Alphabet of an [`EngineConfig`], either one of the built-in alphabets or a custom one made
+/// of 64 ASCII symbols.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum AlphabetConfig {
+    Standard,
+    UrlSafe,
+    Custom(String),
+}
+
+/// Plain description of a [`Base64Engine`], meant to be persisted (with the `serde` feature)
+/// and turned back into an engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EngineConfig {
+    pub alphabet: AlphabetConfig,
+    pub padding: Option<char>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub require_canonical: bool,
+    #[cfg_attr(feature = "serde", serde(default = "default_exact_capacity"))]
+    pub exact_capacity: bool,
+}
+
+// engines reserve their output up front unless told otherwise
+#[cfg(feature = "serde")]
+fn default_exact_capacity() -> bool {
+    true
+}
+
+impl TryFrom<EngineConfig> for Base64Engine {
+    type Error = AlphabetError;
+
+    fn try_from(config: EngineConfig) -> Result<Self, Self::Error> {
+        let alphabet = match &config.alphabet {
+            AlphabetConfig::Standard => &Base64::ALPHABET_STANDARD[..],
+            AlphabetConfig::UrlSafe => &Base64::ALPHABET_URL_SAFE[..],
+            AlphabetConfig::Custom(alphabet) => alphabet.as_bytes(),
+        };
+
+        let engine = Base64Engine::new(alphabet, config.padding)?;
+        Ok(engine
+            .require_canonical(config.require_canonical)
+            .exact_capacity(config.exact_capacity))
+    }
+}
+
+impl From<&Base64Engine> for EngineConfig {
+    fn from(engine: &Base64Engine) -> Self {
+        let alphabet = match engine.alphabet {
+            alphabet if alphabet == Base64::ALPHABET_STANDARD => AlphabetConfig::Standard,
+            alphabet if alphabet == Base64::ALPHABET_URL_SAFE => AlphabetConfig::UrlSafe,
+            alphabet => AlphabetConfig::Custom(alphabet.iter().copied().map(char::from).collect()),
+        };
+
+        Self {
+            alphabet,
+            padding: engine.padding.map(char::from),
+            require_canonical: engine.require_canonical,
+            exact_capacity: engine.exact_capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engine_config_works() {
+        let engine = Base64::url_safe_no_pad().require_canonical(true);
+        let config = EngineConfig::from(&engine);
+        let expected = EngineConfig {
+            alphabet: AlphabetConfig::UrlSafe,
+            padding: None,
+            require_canonical: true,
+            exact_capacity: true,
+        };
+        assert_eq!(config, expected);
+        assert_eq!(Base64Engine::try_from(config), Ok(engine));
+
+        let config = EngineConfig {
+            alphabet: AlphabetConfig::Custom(String::from("ABC")),
+            padding: Some('='),
+            require_canonical: false,
+            exact_capacity: true,
+        };
+        let err = Base64Engine::try_from(config).unwrap_err();
+        assert_eq!(err, AlphabetError::InvalidLength { len: 3 });
+
+        let growing = Base64::standard().exact_capacity(false);
+        let config = EngineConfig::from(&growing);
+        assert!(!config.exact_capacity);
+        assert_eq!(Base64Engine::try_from(config), Ok(growing));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn engine_config_serde_works() {
+        let mut alphabet = Base64::ALPHABET_STANDARD;
+        alphabet.swap(0, 63);
+        let engine = Base64Engine::new(&alphabet, None).expect("should be a valid alphabet");
+
+        let json = r#"{"alphabet":"url-safe","padding":null}"#;
+        let config = serde_json::from_str::<EngineConfig>(json).expect("should deserialize");
+        let decoded = Base64Engine::try_from(config).expect("should be a valid config");
+        assert_eq!(decoded, Base64::url_safe_no_pad());
+
+        let json = r#"{"alphabet":"standard","padding":"=","exact_capacity":false}"#;
+        let config = serde_json::from_str::<EngineConfig>(json).expect("should deserialize");
+        let decoded = Base64Engine::try_from(config).expect("should be a valid config");
+        assert_eq!(decoded, Base64::standard().exact_capacity(false));
+
+        let json = serde_json::to_string(&EngineConfig::from(&engine)).expect("should serialize");
+        let config = serde_json::from_str::<EngineConfig>(&json).expect("should deserialize");
+        let rebuilt = Base64Engine::try_from(config).expect("should be a valid config");
+        assert_eq!(rebuilt, engine);
+        assert_eq!(rebuilt.encode(b"light work"), engine.encode(b"light work"));
+    }
+}
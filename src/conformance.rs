@@ -0,0 +1,121 @@
+// Conformance tests against the test vectors of RFC 4648, section 10.
+
+use crate::{Base64, Base64Engine};
+
+const VECTORS: [(&str, &str); 7] = [
+    ("", ""),
+    ("f", "Zg=="),
+    ("fo", "Zm8="),
+    ("foo", "Zm9v"),
+    ("foob", "Zm9vYg=="),
+    ("fooba", "Zm9vYmE="),
+    ("foobar", "Zm9vYmFy"),
+];
+
+fn check_vectors(engine: &Base64Engine, padded: bool) {
+    for (input, output) in VECTORS {
+        let output = if padded {
+            output
+        } else {
+            output.trim_end_matches('=')
+        };
+
+        assert_eq!(engine.encode(input), output, "encoding {input:?}");
+        assert_eq!(
+            engine.decode(output).as_deref(),
+            Ok(input.as_bytes()),
+            "decoding {output:?}"
+        );
+    }
+}
+
+#[test]
+fn standard_matches_rfc4648_vectors() {
+    check_vectors(&Base64::standard(), true);
+}
+
+#[test]
+fn url_safe_matches_rfc4648_vectors() {
+    check_vectors(&Base64::url_safe(), true);
+    check_vectors(&Base64::url_safe_no_pad(), false);
+}
+
+// the encode and decode loops check their indexing with `debug_assert!`, which only runs in
+// debug builds, so make sure the vectors went through them
+#[test]
+#[cfg(debug_assertions)]
+fn debug_assertions_hold_for_rfc4648_vectors() {
+    let engines = [
+        (Base64::standard().require_canonical(true), true),
+        (Base64::url_safe(), true),
+        (Base64::url_safe_no_pad(), false),
+        (Base64::imap(), false),
+    ];
+    for (engine, padded) in engines {
+        check_vectors(&engine, padded);
+
+        let mut out = [0u8; 8];
+        for (input, _) in VECTORS {
+            let len = engine.encode_to_slice(input, &mut out).expect("should fit");
+            assert_eq!(len, engine.encoded_len(input.len()));
+            assert_eq!(
+                engine.decode_to_slice(&out[..len], &mut out.clone()),
+                Ok(input.len())
+            );
+        }
+    }
+}
+
+// the symbol of value 0 is a real symbol, so it must never be dropped or mistaken for padding,
+// wherever it shows up in the input
+const ZERO_VECTORS: [(&[u8], &str); 8] = [
+    (&[0], "AA=="),
+    (&[0, 0], "AAA="),
+    (&[0, 0, 0], "AAAA"),
+    (&[0, 0, 0, 0], "AAAAAA=="),
+    (&[0, 0, 1], "AAAB"),
+    (&[1, 0, 0], "AQAA"),
+    (&[0, 1, 0, 0, 0], "AAEAAAA="),
+    (&[b'A', 0], "QQA="),
+];
+
+#[test]
+fn standard_keeps_zero_index_symbol() {
+    let engine = Base64::standard();
+    assert_eq!(&engine.encode_const::<8>(&[0, 0, 0, 0]), b"AAAAAA==");
+    assert_eq!(engine.decode_const::<4>(b"AAAAAA=="), [0, 0, 0, 0]);
+
+    for (input, output) in ZERO_VECTORS {
+        assert_eq!(engine.encode(input), output, "encoding {input:?}");
+        assert_eq!(
+            engine.decode(output).as_deref(),
+            Ok(input),
+            "decoding {output:?}"
+        );
+    }
+}
+
+#[test]
+fn custom_alphabet_keeps_zero_index_symbol() {
+    // alphabet of crypt(3), whose symbol of value 0 is `.`
+    let alphabet = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    let engine = Base64Engine::new(alphabet, Some('=')).expect("should be a valid alphabet");
+
+    for (input, output) in ZERO_VECTORS {
+        let expected = Base64::standard()
+            .transcode(output, &engine)
+            .expect("should transcode");
+        assert!(expected.starts_with('.') || input[0] != 0);
+
+        assert_eq!(engine.encode(input), expected, "encoding {input:?}");
+        assert_eq!(
+            engine.decode(&expected).as_deref(),
+            Ok(input),
+            "decoding {expected:?}"
+        );
+    }
+
+    assert_eq!(engine.encode([0, 0, 0]), "....");
+    assert_eq!(engine.decode("....").as_deref(), Ok(&[0, 0, 0][..]));
+    assert_eq!(engine.decode("..=="), Ok(alloc::vec![0]));
+}
@@ -0,0 +1,119 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::Base64Engine;
+
+impl Base64Engine {
+    /// Encodes `bytes` straight into `f`, through a small stack buffer instead of an allocated
+    /// `String`.
+    pub fn encode_fmt(&self, bytes: impl AsRef<[u8]>, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut buf = [0u8; 256];
+        let mut len = 0;
+        let mut result = Ok(());
+
+        self.encode_each(bytes.as_ref(), |byte| {
+            buf[len] = byte;
+            len += 1;
+            if len == buf.len() {
+                // once `f` fails there is no point in writing anything else
+                if result.is_ok() {
+                    result = write_ascii(f, &buf);
+                }
+                len = 0;
+            }
+        });
+
+        result?;
+        write_ascii(f, &buf[..len])
+    }
+
+    /// Encodes `input` as a JSON string literal, surrounding quotes included, e.g. to embed it
+    /// in hand-built JSON.
+    ///
+    /// The `/` of the standard alphabet is left unescaped, as JSON allows. Only custom
+    /// alphabets with `"`, `\` or control symbols need escaping, done as JSON requires.
+    pub fn encode_json_string(&self, input: impl AsRef<[u8]>) -> String {
+        let input = input.as_ref();
+        let mut json = String::with_capacity(self.reserved_len(self.encoded_len(input.len()) + 2));
+
+        json.push('"');
+        self.encode_each(input, |byte| match byte {
+            b'"' => json.push_str("\\\""),
+            b'\\' => json.push_str("\\\\"),
+            byte if byte.is_ascii_control() => {
+                // infallible, writing into a `String` never fails
+                let _ = write!(json, "\\u{byte:04x}");
+            }
+            byte => json.push(char::from(byte)),
+        });
+        json.push('"');
+
+        json
+    }
+
+    /// Returns an adapter that encodes `input` as it is formatted, e.g. to log binary data
+    /// without materializing its encoding.
+    pub fn display<'a>(&'a self, input: &'a [u8]) -> impl fmt::Display + 'a {
+        Encoded {
+            engine: self,
+            input,
+        }
+    }
+}
+
+struct Encoded<'a> {
+    engine: &'a Base64Engine,
+    input: &'a [u8],
+}
+
+impl fmt::Display for Encoded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.engine.encode_fmt(self.input, f)
+    }
+}
+
+// encoded output is always ASCII, so it never fails to convert
+fn write_ascii(f: &mut impl fmt::Write, ascii: &[u8]) -> fmt::Result {
+    let ascii = core::str::from_utf8(ascii).map_err(|_| fmt::Error)?;
+    f.write_str(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::{Base64, Base64Engine};
+
+    #[test]
+    fn encode_json_string_works() {
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+        let json = Base64::standard().encode_json_string(input);
+        assert_eq!(json, r#""+/+//g==""#);
+
+        let parsed = serde_json::from_str::<String>(&json).expect("should be a JSON string");
+        assert_eq!(parsed, Base64::standard().encode(input));
+
+        let mut alphabet = *Base64::standard().alphabet().as_bytes();
+        alphabet[62] = b'"';
+        alphabet[63] = 0x7f;
+        let engine = Base64Engine::new(&alphabet, Some('\\')).expect("should be a valid alphabet");
+        let json = engine.encode_json_string(input);
+        assert_eq!(json, r#""\"\u007f\"\u007f\u007fg\\\\""#);
+
+        let parsed = serde_json::from_str::<String>(&json).expect("should be a JSON string");
+        assert_eq!(parsed, engine.encode(input));
+    }
+
+    #[test]
+    fn display_matches_encode() {
+        let engine = Base64::standard();
+        let input = (0..1000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+
+        for len in [0, 1, 2, 3, 191, 192, 193, 1000] {
+            let formatted = format!("{}", engine.display(&input[..len]));
+            assert_eq!(formatted, engine.encode(&input[..len]));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Base64, Base64Engine, DecodeError};
+
+/// Engine selected at compile time through a type rather than passed around at runtime, so
+/// its alphabet is a constant the compiler can inline.
+pub trait Engine {
+    const ENGINE: Base64Engine;
+
+    fn encode(bytes: impl AsRef<[u8]>) -> String {
+        Self::ENGINE.encode(bytes)
+    }
+
+    fn decode(encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        Self::ENGINE.decode(encoded)
+    }
+}
+
+/// Compile-time counterpart of [`Base64::standard`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StandardEngine;
+
+impl Engine for StandardEngine {
+    const ENGINE: Base64Engine = Base64::STANDARD;
+}
+
+/// Compile-time counterpart of [`Base64::url_safe`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UrlSafeEngine;
+
+impl Engine for UrlSafeEngine {
+    const ENGINE: Base64Engine = Base64::URL_SAFE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_engines_match_runtime_engines() {
+        let input = (0..=255u8).collect::<Vec<u8>>();
+
+        for len in [0, 1, 2, 3, 255, 256] {
+            let standard = StandardEngine::encode(&input[..len]);
+            assert_eq!(standard, Base64::standard().encode(&input[..len]));
+            assert_eq!(
+                StandardEngine::decode(&standard).as_deref(),
+                Ok(&input[..len])
+            );
+
+            let url_safe = UrlSafeEngine::encode(&input[..len]);
+            assert_eq!(url_safe, Base64::url_safe().encode(&input[..len]));
+            assert_eq!(
+                UrlSafeEngine::decode(&url_safe).as_deref(),
+                Ok(&input[..len])
+            );
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A byte that is neither part of the alphabet nor padding, found at `index` of the input.
+    InvalidByte { index: usize, byte: u8 },
+    /// Same as `InvalidByte`, but for a `byte` that is a symbol of the other built-in alphabet,
+    /// e.g. standard base64 given to a url-safe engine.
+    WrongAlphabet { byte: u8, index: usize },
+    /// Invalid input that looks like base32, likely given to a base64 decoder by mistake.
+    LikelyBase32,
+    /// Input whose length can not be produced by the engine, e.g. unpadded input for an engine
+    /// that requires padding.
+    InvalidLength,
+    /// Padding found at `index` anywhere other than the end of the input.
+    InvalidPadding { index: usize },
+    /// Last meaningful symbol, at `index`, with non-zero unused bits.
+    NonCanonical { index: usize },
+    /// Input that would decode to `len` bytes, more than the allowed `budget`.
+    ExceedsBudget { len: usize, budget: usize },
+    /// Output buffer of `capacity` bytes, too small for the `len` decoded bytes.
+    OutputTooSmall { len: usize, capacity: usize },
+    /// Input decoding to `got` bytes instead of the `expected` ones.
+    UnexpectedLength { got: usize, expected: usize },
+    /// Decoded output of `len` bytes, not a whole number of elements of `size` bytes.
+    UnalignedLength { len: usize, size: usize },
+    /// Decoded byte `index` past the `len` bytes the input decodes to.
+    ByteIndexOutOfRange { index: usize, len: usize },
+    /// Decoded output of `len` bytes with a CRC-32 of `crc32`, not matching the expected ones.
+    IntegrityMismatch { len: usize, crc32: u32 },
+    /// Check symbol, at `index`, that does not match the symbols preceding it.
+    InvalidChecksum { index: usize },
+    /// PEM input without a well formed `-----BEGIN ...-----` / `-----END ...-----` pair.
+    InvalidPem,
+    /// PEM input whose BEGIN and END labels differ.
+    PemLabelMismatch { begin: String, end: String },
+}
+
+impl DecodeError {
+    /// Shifts the index reported by the error by `offset`, for errors found in a part of the
+    /// input starting at `offset`.
+    pub(crate) fn with_offset(self, offset: usize) -> Self {
+        self.map_index(|index| index + offset)
+    }
+
+    /// Replaces the index reported by the error with `f(index)`, e.g. to translate it back to
+    /// the original input when some of its bytes were dropped before decoding.
+    pub(crate) fn map_index(self, f: impl FnOnce(usize) -> usize) -> Self {
+        match self {
+            Self::InvalidByte { index, byte } => Self::InvalidByte {
+                index: f(index),
+                byte,
+            },
+            Self::WrongAlphabet { byte, index } => Self::WrongAlphabet {
+                byte,
+                index: f(index),
+            },
+            Self::InvalidPadding { index } => Self::InvalidPadding { index: f(index) },
+            Self::NonCanonical { index } => Self::NonCanonical { index: f(index) },
+            Self::InvalidChecksum { index } => Self::InvalidChecksum { index: f(index) },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidByte { index, byte } => {
+                write!(f, "invalid base64 byte {byte:#04x} at index {index}")
+            }
+            Self::WrongAlphabet { byte, index } => write!(
+                f,
+                "invalid base64 byte {byte:#04x} at index {index}, a symbol of another \
+                 alphabet, e.g. standard base64 in a url-safe decoder"
+            ),
+            Self::InvalidLength => write!(f, "invalid base64 input length"),
+            Self::LikelyBase32 => write!(f, "invalid base64 input, which looks like base32"),
+            Self::InvalidPadding { index } => write!(f, "invalid base64 padding at index {index}"),
+            Self::NonCanonical { index } => {
+                write!(f, "non-canonical base64 symbol at index {index}")
+            }
+            Self::ExceedsBudget { len, budget } => {
+                write!(f, "decoded length {len} exceeds budget of {budget} bytes")
+            }
+            Self::OutputTooSmall { len, capacity } => {
+                write!(
+                    f,
+                    "decoded length {len} exceeds output capacity of {capacity} bytes"
+                )
+            }
+            Self::UnexpectedLength { got, expected } => {
+                write!(f, "decoded {got} bytes instead of the expected {expected}")
+            }
+            Self::UnalignedLength { len, size } => {
+                write!(f, "decoded length {len} is not a multiple of {size} bytes")
+            }
+            Self::ByteIndexOutOfRange { index, len } => {
+                write!(f, "byte index {index} out of range for {len} decoded bytes")
+            }
+            Self::IntegrityMismatch { len, crc32 } => write!(
+                f,
+                "decoded output of {len} bytes with crc32 {crc32:#010x} does not match the \
+                 expected one"
+            ),
+            Self::InvalidChecksum { index } => {
+                write!(f, "base64 check symbol at index {index} does not match")
+            }
+            Self::InvalidPem => write!(f, "missing or malformed PEM armor"),
+            Self::PemLabelMismatch { begin, end } => {
+                write!(f, "PEM label mismatch: BEGIN {begin} but END {end}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Reports the error as [`std::io::ErrorKind::InvalidData`], for `?` in `std::io` adapters.
+#[cfg(feature = "std")]
+impl From<DecodeError> for std::io::Error {
+    fn from(err: DecodeError) -> Self {
+        Self::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Error of decoding base64 from a fallible source, either an error of the source itself or
+/// one of decoding what it yielded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeTryError<E> {
+    Source(E),
+    Decode(DecodeError),
+}
+
+impl<E> From<DecodeError> for DecodeTryError<E> {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for DecodeTryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(err) => write!(f, "base64 source error: {err}"),
+            Self::Decode(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for DecodeTryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Source(err) => Some(err),
+            Self::Decode(err) => Some(err),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    /// Encoded output of `len` chars that does not fit in a field of `width` chars.
+    ExceedsWidth { len: usize, width: usize },
+    /// Output buffer of `capacity` bytes, too small for the `len` encoded bytes.
+    OutputTooSmall { len: usize, capacity: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExceedsWidth { len, width } => {
+                write!(f, "encoded length {len} exceeds field width {width}")
+            }
+            Self::OutputTooSmall { len, capacity } => {
+                write!(
+                    f,
+                    "encoded length {len} exceeds output capacity of {capacity} bytes"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// Reports the error as [`std::io::ErrorKind::InvalidInput`], for `?` in `std::io` adapters.
+#[cfg(feature = "std")]
+impl From<EncodeError> for std::io::Error {
+    fn from(err: EncodeError) -> Self {
+        Self::new(std::io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// Fixed capacity output of `capacity` bytes, too small for the `len` encoded bytes, e.g. a
+/// `heapless::String` given to `encode_heapless`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { len, capacity } = self;
+        write!(
+            f,
+            "encoded length {len} exceeds capacity of {capacity} bytes"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// Alphabet of `len` symbols instead of 64.
+    InvalidLength { len: usize },
+    /// Symbol that is not ASCII, so it can not be emitted as a single char.
+    NonAsciiSymbol { byte: u8 },
+    /// Symbol that shows up more than once in the alphabet.
+    DuplicateSymbol { byte: u8 },
+    /// Symbol expected to be part of the alphabet, but which is not.
+    MissingSymbol { byte: u8 },
+    /// Padding that is also a symbol of the alphabet.
+    PaddingInAlphabet { byte: u8 },
+    /// Padding that is not ASCII, so it can not be emitted as a single byte.
+    NonAsciiPadding { padding: char },
+    /// 6-bit `value` that is not decoded from exactly one symbol, the one encoding it.
+    IncompleteMapping { value: u8 },
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { len } => {
+                write!(f, "alphabet has {len} symbols instead of 64")
+            }
+            Self::NonAsciiSymbol { byte } => write!(f, "non-ASCII alphabet symbol {byte:#04x}"),
+            Self::DuplicateSymbol { byte } => write!(f, "duplicate alphabet symbol {byte:#04x}"),
+            Self::MissingSymbol { byte } => write!(f, "missing alphabet symbol {byte:#04x}"),
+            Self::PaddingInAlphabet { byte } => {
+                write!(f, "padding {byte:#04x} is also an alphabet symbol")
+            }
+            Self::NonAsciiPadding { padding } => write!(f, "non-ASCII padding {padding:?}"),
+            Self::IncompleteMapping { value } => {
+                write!(f, "6-bit value {value} is not mapped to exactly one symbol")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AlphabetError {}
+
+/// Name that is not the one of any built-in [`Variant`](crate::Variant).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownVariant {
+    pub name: String,
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown base64 variant {:?}", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownVariant {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn converts_into_io_error() {
+        let err = io::Error::from(DecodeError::InvalidByte {
+            index: 4,
+            byte: b'*',
+        });
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid base64 byte 0x2a at index 4");
+
+        let err = io::Error::from(EncodeError::OutputTooSmall {
+            len: 8,
+            capacity: 4,
+        });
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "encoded length 8 exceeds output capacity of 4 bytes"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::vec::Vec;
+
+use crate::Base64Engine;
+
+/// How a single group of up to 3 input bytes is encoded, as returned by
+/// [`Base64Engine::explain`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupExplain {
+    /// Input bytes of the group, zero past `len`.
+    pub bytes: [u8; 3],
+    /// Amount of input bytes of the group, less than 3 only for the last one.
+    pub len: usize,
+    /// `bytes` merged big-endian, first byte in the most significant bits.
+    pub merged: u32,
+    /// 6-bit values extracted from `merged`, first symbol in the most significant bits.
+    pub indices: [u8; 4],
+    /// Symbols of `indices`, those past the first `len + 1` replaced with padding if the engine
+    /// has any. Engines without padding only output the first `len + 1`.
+    pub symbols: [u8; 4],
+}
+
+impl Base64Engine {
+    /// Returns how each group of 3 bytes of `input` is merged, split into 6-bit values and
+    /// mapped to symbols, e.g. to debug bit order issues.
+    pub fn explain(&self, input: &[u8]) -> Vec<GroupExplain> {
+        input
+            .chunks(3)
+            .map(|chunk| {
+                let mut bytes = [0u8; 3];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                let merged = self.merge_encode_bytes(bytes[0], bytes[1], bytes[2]);
+                let indices =
+                    Self::ENCODE_RSH.map(|rsh| ((merged >> rsh) & Self::ENCODE_MASK) as u8);
+
+                let mut symbols = indices.map(|idx| self.alphabet[usize::from(idx)]);
+                if let Some(padding) = self.padding {
+                    symbols[chunk.len() + 1..].fill(padding);
+                }
+
+                GroupExplain {
+                    bytes,
+                    len: chunk.len(),
+                    merged,
+                    indices,
+                    symbols,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn explain_works() {
+        let engine = Base64::standard();
+        let explained = engine.explain(b"ligh");
+        assert_eq!(
+            explained,
+            [
+                GroupExplain {
+                    bytes: *b"lig",
+                    len: 3,
+                    merged: 0x6c6967,
+                    indices: [27, 6, 37, 39],
+                    symbols: *b"bGln",
+                },
+                GroupExplain {
+                    bytes: [b'h', 0, 0],
+                    len: 1,
+                    merged: 0x680000,
+                    indices: [26, 0, 0, 0],
+                    symbols: *b"aA==",
+                },
+            ]
+        );
+
+        let symbols = explained.iter().flat_map(|group| group.symbols);
+        assert!(symbols.eq(engine.encode("ligh").bytes()));
+    }
+}
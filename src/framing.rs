@@ -0,0 +1,501 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Base64, Base64Engine, DecodeError, EncodeState};
+
+impl Base64Engine {
+    const FRAME_PREFIX_LEN: usize = 4;
+    pub(crate) const TERMINATOR: u8 = b'\n';
+    // the longest LEB128 encoding of a u64, 7 bits per byte
+    const VARINT_MAX_LEN: usize = 10;
+
+    /// Encodes `input` unpadded, prefixed with a single symbol holding `input.len() % 3`, so
+    /// that the output is self-describing without padding.
+    pub fn encode_compact(&self, input: &[u8]) -> String {
+        let unpadded = self.clone().without_padding();
+
+        let mut encoded =
+            String::with_capacity(self.reserved_len(1 + unpadded.encoded_len(input.len())));
+        encoded.push(char::from(self.alphabet[input.len() % 3]));
+        unpadded.encode_into(input, &mut encoded);
+
+        encoded
+    }
+
+    /// Decodes the output of [`Base64Engine::encode_compact`], checking the body against the
+    /// remainder stored in its first symbol.
+    pub fn decode_compact(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let (header, body) = encoded
+            .as_ref()
+            .split_first()
+            .ok_or(DecodeError::InvalidLength)?;
+
+        let remaining = match self.decode_table[usize::from(*header)] {
+            remaining @ 0..=2 => usize::from(remaining),
+            _ => {
+                return Err(DecodeError::InvalidByte {
+                    index: 0,
+                    byte: *header,
+                })
+            }
+        };
+
+        let unpadded = self.clone().without_padding();
+        let decoded = unpadded.decode(body).map_err(|err| err.with_offset(1))?;
+        if decoded.len() % 3 != remaining {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        Ok(decoded)
+    }
+
+    /// Encodes `input` prefixed with its length as a 4-byte big-endian integer, so that framed
+    /// encodings can be concatenated and decoded one at a time with
+    /// [`Base64Engine::decode_framed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` is longer than `u32::MAX` bytes.
+    pub fn encode_framed(&self, input: &[u8]) -> String {
+        let len = u32::try_from(input.len()).expect("frame payload longer than u32::MAX bytes");
+        let mut encoded = String::with_capacity(
+            self.reserved_len(self.encoded_len(Self::FRAME_PREFIX_LEN + input.len())),
+        );
+
+        let mut state = EncodeState::new(self.clone());
+        state.push(&len.to_be_bytes(), &mut encoded);
+        state.push(input, &mut encoded);
+        state.finalize(&mut encoded);
+
+        encoded
+    }
+
+    /// Decodes the payload of the frame at the start of `encoded`, as produced by
+    /// [`Base64Engine::encode_framed`]. Errors if `encoded` is shorter than the frame.
+    ///
+    /// Anything following the frame is left untouched, the next frame, if any, starts at
+    /// `encoded_len(4 + payload.len())`.
+    pub fn decode_framed(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let encoded = encoded.as_ref();
+
+        // the first 6 symbols carry the whole prefix, plus 4 bits of whatever follows it
+        let header = encoded.get(..6).ok_or(DecodeError::InvalidLength)?;
+        let unpadded = self.clone().without_padding().require_canonical(false);
+        let mut prefix = [0u8; Self::FRAME_PREFIX_LEN];
+        unpadded.decode_to_slice(header, &mut prefix)?;
+
+        let frame_len = usize::try_from(u32::from_be_bytes(prefix))
+            .ok()
+            .and_then(|len| len.checked_add(Self::FRAME_PREFIX_LEN))
+            .map(|len| self.encoded_len(len))
+            .ok_or(DecodeError::InvalidLength)?;
+        let frame = encoded.get(..frame_len).ok_or(DecodeError::InvalidLength)?;
+
+        let mut decoded = self.decode(frame)?;
+        decoded.drain(..Self::FRAME_PREFIX_LEN);
+        Ok(decoded)
+    }
+
+    /// Same as [`Base64Engine::encode_framed`], but prefixed with the length as an unsigned
+    /// LEB128 varint, a single byte for payloads shorter than 128 bytes. Framed encodings are
+    /// decoded one at a time with [`Base64Engine::decode_varint_framed`].
+    pub fn encode_varint_framed(&self, input: &[u8]) -> String {
+        let mut prefix = [0u8; Self::VARINT_MAX_LEN];
+        let mut prefix_len = 0;
+        let mut len = input.len() as u64;
+        loop {
+            // 7 bits per byte, least significant first, the high bit set on all but the last
+            prefix[prefix_len] = (len & 0x7f) as u8;
+            len >>= 7;
+            if len == 0 {
+                break;
+            }
+            prefix[prefix_len] |= 0x80;
+            prefix_len += 1;
+        }
+        let prefix = &prefix[..=prefix_len];
+
+        let mut encoded =
+            String::with_capacity(self.reserved_len(self.encoded_len(prefix.len() + input.len())));
+        let mut state = EncodeState::new(self.clone());
+        state.push(prefix, &mut encoded);
+        state.push(input, &mut encoded);
+        state.finalize(&mut encoded);
+
+        encoded
+    }
+
+    /// Decodes the payload of the frame at the start of `encoded`, as produced by
+    /// [`Base64Engine::encode_varint_framed`]. Errors if `encoded` is shorter than the frame or
+    /// its length prefix is longer than 10 bytes.
+    ///
+    /// Anything following the frame is left untouched, the next frame, if any, starts at
+    /// `encoded_len(prefix_len + payload.len())`.
+    pub fn decode_varint_framed(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let encoded = encoded.as_ref();
+
+        // the prefix is decoded symbol by symbol, stopping at the byte that clears the
+        // continuation bit, so the symbols following it are only read by `decode`
+        let mut symbols = encoded.iter().enumerate();
+        let (mut bits, mut bits_len) = (0u16, 0);
+        let mut payload_len = 0u64;
+        let mut prefix_len = 0;
+        loop {
+            while bits_len < 8 {
+                let (index, &byte) = symbols.next().ok_or(DecodeError::InvalidLength)?;
+                let value = match self.decode_table[usize::from(byte)] {
+                    // padding ends the encoding before the prefix does
+                    Self::INVALID_SYMBOL if self.padding == Some(byte) => {
+                        return Err(DecodeError::InvalidLength);
+                    }
+                    Self::INVALID_SYMBOL if Self::is_builtin_symbol(byte) => {
+                        return Err(DecodeError::WrongAlphabet { byte, index });
+                    }
+                    Self::INVALID_SYMBOL => return Err(DecodeError::InvalidByte { index, byte }),
+                    value => value,
+                };
+                bits = bits << 6 | u16::from(value);
+                bits_len += 6;
+            }
+
+            bits_len -= 8;
+            let byte = (bits >> bits_len) as u8;
+            bits &= (1 << bits_len) - 1;
+
+            payload_len |= u64::from(byte & 0x7f) << (7 * prefix_len);
+            prefix_len += 1;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            if prefix_len == Self::VARINT_MAX_LEN {
+                return Err(DecodeError::InvalidLength);
+            }
+        }
+
+        let frame_len = usize::try_from(payload_len)
+            .ok()
+            .and_then(|len| len.checked_add(prefix_len))
+            .map(|len| self.encoded_len(len))
+            .ok_or(DecodeError::InvalidLength)?;
+        let frame = encoded.get(..frame_len).ok_or(DecodeError::InvalidLength)?;
+
+        let mut decoded = self.decode(frame)?;
+        decoded.drain(..prefix_len);
+        Ok(decoded)
+    }
+
+    /// Encodes `input` followed by a `\n` terminator, which is never part of the built-in
+    /// alphabets, so a stream of terminated messages can be split back into messages with
+    /// [`TerminatedMessages`](crate::TerminatedMessages), even after a corrupted one.
+    pub fn encode_terminated(&self, input: impl AsRef<[u8]>) -> String {
+        let input = input.as_ref();
+        let mut encoded =
+            String::with_capacity(self.reserved_len(self.encoded_len(input.len()) + 1));
+        self.encode_into(input, &mut encoded);
+        encoded.push(char::from(Self::TERMINATOR));
+
+        encoded
+    }
+
+    /// Encodes the big-endian bytes of `n`, leading zero bytes stripped, as unpadded url-safe
+    /// base64, e.g. for short ids in URLs. Zero is encoded as a single zero byte.
+    ///
+    /// The output is the same whatever the alphabet and padding of this engine.
+    pub fn encode_u64(&self, n: u64) -> String {
+        let bytes = n.to_be_bytes();
+        let leading = (n.leading_zeros() / 8).min(7) as usize;
+
+        Base64::url_safe_no_pad().encode(&bytes[leading..])
+    }
+
+    /// Decodes the output of [`Base64Engine::encode_u64`], unpadded url-safe base64. The
+    /// alphabet, padding and flags of this engine are ignored, like in `encode_u64`.
+    ///
+    /// Only the output of `encode_u64` is accepted, so every value has a single encoding: it
+    /// errors if `encoded` does not decode to 1 to 8 bytes, if its last symbol is not canonical,
+    /// if it has a leading zero byte other than the one of zero itself, which is reported as
+    /// [`DecodeError::InvalidLength`], or if it has a trailing line break.
+    pub fn decode_u64(&self, encoded: impl AsRef<[u8]>) -> Result<u64, DecodeError> {
+        let encoded = encoded.as_ref();
+        let trimmed = Self::trim_newline(encoded);
+        if trimmed.len() != encoded.len() {
+            let index = trimmed.len();
+            return Err(DecodeError::InvalidByte {
+                index,
+                byte: encoded[index],
+            });
+        }
+
+        let unpadded = Base64::url_safe_no_pad().require_canonical(true);
+        let (decoded, len) = unpadded
+            .decode_to_array::<8>(encoded)
+            .map_err(|err| match err {
+                DecodeError::OutputTooSmall { .. } => DecodeError::InvalidLength,
+                err => err,
+            })?;
+        if len == 0 {
+            return Err(DecodeError::InvalidLength);
+        }
+        // longer than the encoding of the same value without its leading zero bytes
+        if len > 1 && decoded[0] == 0 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut bytes = [0u8; 8];
+        bytes[8 - len..].copy_from_slice(&decoded[..len]);
+        Ok(u64::from_be_bytes(bytes))
+    }
+
+    /// Decodes `encoded` made of several independently padded encodings joined together, e.g.
+    /// `QQ==QUI=`, each decoded to its own bytes before being concatenated.
+    ///
+    /// A window ending with padding closes its chunk. Engines without padding can not tell
+    /// chunks apart, so it is the same as `decode` for them.
+    pub fn decode_concatenated(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let Some(padding) = self.padding else {
+            return self.decode(encoded);
+        };
+        // same single trailing line break tolerance as `decode`
+        let encoded = Self::trim_newline(encoded.as_ref());
+        if !encoded.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut decoded = Vec::with_capacity(self.reserved_len(encoded.len() / 4 * 3));
+        let mut start = 0;
+        for end in (4..=encoded.len()).step_by(4) {
+            if encoded[end - 1] == padding || end == encoded.len() {
+                self.decode_each(&encoded[start..end], |window| {
+                    decoded.extend_from_slice(window)
+                })
+                .map_err(|err| err.with_offset(start))?;
+                start = end;
+            }
+        }
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::{Base64, DecodeError};
+
+    #[test]
+    fn compact_works() {
+        let engine = Base64::standard();
+        let config = [
+            ("light wor", "AbGlnaHQgd29y"),
+            ("light w", "BbGlnaHQgdw"),
+            ("light wo", "CbGlnaHQgd28"),
+        ];
+
+        for (input, output) in config {
+            let encoded = engine.encode_compact(input.as_bytes());
+            assert_eq!(encoded, output);
+            assert_eq!(
+                engine.decode_compact(&encoded),
+                Ok(input.as_bytes().to_vec())
+            );
+        }
+
+        assert_eq!(
+            engine.decode_compact("AbGlnaHQgdw"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(engine.decode_compact(""), Err(DecodeError::InvalidLength));
+        let err = engine.decode_compact("DbGln").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 0,
+                byte: b'D'
+            }
+        );
+    }
+
+    #[test]
+    fn framed_works() {
+        let engine = Base64::standard();
+        let messages: [&[u8]; 4] = [b"", b"l", b"light w", b"light work"];
+
+        let concatenated = messages
+            .iter()
+            .map(|message| engine.encode_framed(message))
+            .collect::<String>();
+
+        let mut rest = concatenated.as_bytes();
+        for message in messages {
+            let decoded = engine.decode_framed(rest).expect("should decode frame");
+            assert_eq!(decoded, message);
+            rest = &rest[engine.encoded_len(4 + decoded.len())..];
+        }
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn decode_framed_rejects_truncated_frame() {
+        let engine = Base64::url_safe_no_pad();
+        let encoded = engine.encode_framed(b"light work");
+        assert_eq!(
+            engine.decode_framed(&encoded).as_deref(),
+            Ok(&b"light work"[..])
+        );
+
+        let truncated = &encoded[..encoded.len() - 2];
+        assert_eq!(
+            engine.decode_framed(truncated),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(engine.decode_framed("AAA"), Err(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn varint_framed_works() {
+        let long = (0..20_000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let messages: [&[u8]; 6] = [b"", b"l", &long[..127], &long[..128], &long[..300], &long];
+
+        for engine in [Base64::standard(), Base64::url_safe_no_pad()] {
+            let concatenated = messages
+                .iter()
+                .map(|message| engine.encode_varint_framed(message))
+                .collect::<String>();
+
+            let mut rest = concatenated.as_bytes();
+            for (message, prefix_len) in messages.into_iter().zip([1, 1, 1, 2, 2, 3]) {
+                let decoded = engine
+                    .decode_varint_framed(rest)
+                    .expect("should decode frame");
+                assert_eq!(decoded, message);
+                rest = &rest[engine.encoded_len(prefix_len + decoded.len())..];
+            }
+            assert!(rest.is_empty());
+        }
+
+        let engine = Base64::standard();
+        assert_eq!(engine.encode_varint_framed(b"l"), "AWw=");
+        let encoded = engine.encode_varint_framed(&long);
+        assert_eq!(
+            engine.decode_varint_framed(&encoded[..encoded.len() - 4]),
+            Err(DecodeError::InvalidLength)
+        );
+        // the prefix of a frame ending mid window is read without the symbols following it
+        let unpadded = Base64::url_safe_no_pad();
+        assert_eq!(
+            unpadded.decode_varint_framed("AWw,").as_deref(),
+            Ok(&b"l"[..])
+        );
+        assert_eq!(
+            unpadded.decode_varint_framed("A,"),
+            Err(DecodeError::InvalidByte {
+                index: 1,
+                byte: b','
+            })
+        );
+        let unterminated = engine.encode([0xff; 11]);
+        assert_eq!(
+            engine.decode_varint_framed(unterminated),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn decode_concatenated_works() {
+        let engine = Base64::standard();
+        let decoded = engine.decode_concatenated("QQ==QUI=bGlnaHQgdw==");
+        assert_eq!(decoded.as_deref(), Ok(&b"AABlight w"[..]));
+        assert_eq!(
+            engine.decode("QQ==QUI="),
+            Err(DecodeError::InvalidPadding { index: 2 })
+        );
+
+        assert_eq!(
+            engine.decode_concatenated("QQ==\n").as_deref(),
+            Ok(&b"A"[..])
+        );
+        assert_eq!(
+            engine.decode_concatenated("QQ==QUI=\r\n").as_deref(),
+            Ok(&b"AAB"[..])
+        );
+        assert_eq!(
+            engine.decode_concatenated("QQ==\n\n"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            Base64::url_safe_no_pad().decode_concatenated("QUJD\n\n"),
+            Err(DecodeError::InvalidByte {
+                index: 4,
+                byte: b'\n'
+            })
+        );
+
+        let err = engine.decode_concatenated("QQ==Q*I=").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 5,
+                byte: b'*'
+            }
+        );
+        assert_eq!(
+            engine.decode_concatenated("QQ==QUI"),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn u64_works() {
+        let engine = Base64::url_safe();
+        let config = [
+            (0, "AA"),
+            (1, "AQ"),
+            (255, "_w"),
+            (256, "AQA"),
+            (1 << 40, "AQAAAAAA"),
+            (123_456_789, "B1vNFQ"),
+            (u64::MAX, "__________8"),
+        ];
+
+        for (n, output) in config {
+            assert_eq!(engine.encode_u64(n), output);
+            assert_eq!(engine.decode_u64(output), Ok(n));
+        }
+
+        let standard = Base64::standard();
+        assert_eq!(standard.encode_u64(0xfbff), "-_8");
+        assert_eq!(standard.decode_u64("-_8"), Ok(0xfbff));
+        assert!(standard.decode_u64("+/8").is_err());
+
+        // every value has a single encoding
+        assert_eq!(engine.decode_u64("AAE"), Err(DecodeError::InvalidLength));
+        assert_eq!(engine.decode_u64("AAAB"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            engine.decode_u64("AR"),
+            Err(DecodeError::NonCanonical { index: 1 })
+        );
+
+        assert_eq!(
+            engine.decode_u64("AQ\n"),
+            Err(DecodeError::InvalidByte {
+                index: 2,
+                byte: b'\n'
+            })
+        );
+        assert_eq!(
+            engine.decode_u64("AQ\r\n"),
+            Err(DecodeError::InvalidByte {
+                index: 2,
+                byte: b'\r'
+            })
+        );
+
+        assert_eq!(engine.decode_u64(""), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            engine.decode_u64("AQAAAAAAAAAA"),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{Base64Engine, CapacityError};
+
+impl Base64Engine {
+    /// Encodes `input` into a fixed capacity [`heapless::String`], without allocating. Errors if
+    /// the encoded output does not fit in `N` bytes.
+    pub fn encode_heapless<const N: usize>(
+        &self,
+        input: impl AsRef<[u8]>,
+    ) -> Result<heapless::String<N>, CapacityError> {
+        let input = input.as_ref();
+        let len = self.encoded_len(input.len());
+        if len > N {
+            return Err(CapacityError { len, capacity: N });
+        }
+
+        let mut encoded = heapless::Vec::<u8, N>::new();
+        encoded
+            .resize_default(len)
+            .expect("encoded output should fit in N bytes");
+        self.encode_to_slice(input, &mut encoded)
+            .expect("encoded output should fit in N bytes");
+
+        Ok(heapless::String::from_utf8(encoded).expect("encoded output should be ASCII"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Base64, CapacityError};
+
+    #[test]
+    fn encode_heapless_works() {
+        let engine = Base64::standard();
+
+        let encoded = engine.encode_heapless::<32>(b"Many hands make");
+        assert_eq!(encoded.as_deref(), Ok("TWFueSBoYW5kcyBtYWtl"));
+
+        let err = engine
+            .encode_heapless::<32>(b"Many hands make light work")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CapacityError {
+                len: 36,
+                capacity: 32
+            }
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Constants of the inner encoding and decoding loops, e.g. to experiment with custom ones.
+//!
+//! These are implementation details exposed as is, which may change or disappear in any
+//! release, regardless of semver.
+
+use crate::Base64Engine;
+
+/// Bitmask extracting the 6-bit value of a symbol once shifted in place.
+pub const ENCODE_MASK: u32 = Base64Engine::ENCODE_MASK;
+/// Bitmask extracting a byte once shifted in place.
+pub const DECODE_MASK: u32 = Base64Engine::DECODE_MASK;
+/// Right shifts extracting, in order, the 4 symbols of a window of 3 bytes merged big-endian
+/// in a `u32`.
+pub const ENCODE_RSH: [u8; 4] = Base64Engine::ENCODE_RSH;
+/// Right shifts extracting, in order, the 3 bytes of a window of 4 symbols merged big-endian
+/// in a `u32`.
+pub const DECODE_RSH: [u8; 3] = Base64Engine::DECODE_RSH;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internals_are_exposed() {
+        assert_eq!(ENCODE_MASK, 0x3F);
+        assert_eq!(DECODE_MASK, 0xFF);
+        assert_eq!(ENCODE_RSH, [18, 12, 6, 0]);
+        assert_eq!(DECODE_RSH, [16, 8, 0]);
+
+        // "lig" merged big-endian
+        let merged = 0x6c6967u32;
+        let symbols = ENCODE_RSH.map(|rsh| (merged >> rsh) & ENCODE_MASK);
+        assert_eq!(symbols, [27, 6, 37, 39]);
+        assert_eq!(
+            DECODE_RSH.map(|rsh| ((merged >> rsh) & DECODE_MASK) as u8),
+            *b"lig"
+        );
+    }
+}
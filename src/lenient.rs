@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, DecodeError};
+
+/// What [`Base64Engine::decode_with`] does with a byte that is neither a symbol nor padding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidAction {
+    /// Drops the byte, as if it was not part of the input.
+    Skip,
+    /// Stops decoding with [`DecodeError::InvalidByte`].
+    Abort,
+    /// Replaces the byte with the symbol of the given 6-bit value. Values above 63 abort.
+    Substitute(u8),
+}
+
+impl Base64Engine {
+    /// Decodes `input`, calling `on_invalid` with the index and value of every byte that is
+    /// neither a symbol of the alphabet nor padding to decide what to do with it.
+    ///
+    /// Error indexes refer to `input`, skipped bytes included.
+    pub fn decode_with(
+        &self,
+        input: impl AsRef<[u8]>,
+        mut on_invalid: impl FnMut(usize, u8) -> InvalidAction,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        // positions of the kept bytes in `input`, to report errors against it
+        let mut positions = Vec::with_capacity(input.len());
+        let mut kept = Vec::with_capacity(input.len());
+
+        for (index, byte) in input.iter().copied().enumerate() {
+            let valid = self.decode_table[usize::from(byte)] != Self::INVALID_SYMBOL
+                || self.padding == Some(byte);
+            let byte = if valid {
+                byte
+            } else {
+                match on_invalid(index, byte) {
+                    InvalidAction::Skip => continue,
+                    InvalidAction::Substitute(value) if value < 64 => {
+                        self.alphabet[usize::from(value)]
+                    }
+                    InvalidAction::Abort | InvalidAction::Substitute(_) => {
+                        return Err(DecodeError::InvalidByte { index, byte });
+                    }
+                }
+            };
+
+            positions.push(index);
+            kept.push(byte);
+        }
+
+        self.decode(kept)
+            .map_err(|err| err.map_index(|index| positions[index]))
+    }
+
+    /// Decodes `encoded` accepting any amount of trailing padding, none included, as emitted by
+    /// sloppy producers, e.g. `QQ===`. The output length only depends on the symbols.
+    ///
+    /// Errors if the symbols left once the padding is removed can not be produced by any
+    /// encoding, i.e. a single symbol past the last whole window.
+    pub fn decode_lenient(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let encoded = Self::trim_newline(encoded.as_ref());
+        let Some(padding) = self.padding else {
+            return self.decode(encoded);
+        };
+
+        let symbols = encoded.len()
+            - encoded
+                .iter()
+                .rev()
+                .take_while(|byte| **byte == padding)
+                .count();
+        let unpadded = self.clone().without_padding();
+        unpadded
+            .decode(&encoded[..symbols])
+            .map_err(|err| match err {
+                DecodeError::InvalidByte { index, byte } if byte == padding => {
+                    DecodeError::InvalidPadding { index }
+                }
+                err => err,
+            })
+    }
+
+    /// Decodes user-pasted `encoded`, tolerating, in that order:
+    ///
+    /// - a leading UTF-8 byte order mark,
+    /// - leading and trailing ASCII whitespace, but not whitespace in between symbols,
+    /// - any amount of trailing padding, none included, as with
+    ///   [`Base64Engine::decode_lenient`].
+    ///
+    /// Error indexes refer to `encoded`, BOM and whitespace included.
+    pub fn decode_forgiving(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let encoded = encoded.as_ref();
+        let without_bom = encoded.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(encoded);
+        let trimmed = without_bom.trim_ascii_start();
+        let offset = encoded.len() - trimmed.len();
+
+        self.decode_lenient(trimmed.trim_ascii_end())
+            .map_err(|err| err.with_offset(offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn decode_with_skip_works() {
+        let engine = Base64::standard();
+        let mut seen = Vec::new();
+        let decoded = engine.decode_with("bG*lnaH Qgdw==", |index, byte| {
+            seen.push((index, byte));
+            InvalidAction::Skip
+        });
+        assert_eq!(decoded.as_deref(), Ok(&b"light w"[..]));
+        assert_eq!(seen, [(2, b'*'), (7, b' ')]);
+    }
+
+    #[test]
+    fn decode_with_abort_works() {
+        let engine = Base64::standard();
+        let err = engine
+            .decode_with("bGln*HQg", |_, _| InvalidAction::Abort)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 4,
+                byte: b'*'
+            }
+        );
+    }
+
+    #[test]
+    fn decode_with_substitute_works() {
+        let engine = Base64::standard();
+        // `*` stands for the lost `a`, of value 26
+        let decoded = engine.decode_with("bGln*HQg", |_, _| InvalidAction::Substitute(26));
+        assert_eq!(decoded.as_deref(), Ok(&b"light "[..]));
+
+        let err = engine
+            .decode_with("bGln*HQg", |_, _| InvalidAction::Substitute(64))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 4,
+                byte: b'*'
+            }
+        );
+    }
+
+    #[test]
+    fn decode_lenient_accepts_extra_padding() {
+        let engine = Base64::standard();
+        for input in ["QQ", "QQ==", "QQ===", "QQ====", "QQ===\n"] {
+            assert_eq!(engine.decode_lenient(input).as_deref(), Ok(&b"A"[..]));
+        }
+        assert_eq!(engine.decode("QQ==="), Err(DecodeError::InvalidLength));
+        assert_eq!(engine.decode("QQ===="), Err(DecodeError::InvalidLength));
+
+        assert_eq!(
+            engine.decode_lenient("bGlnaHQgdw=="),
+            Ok(b"light w".to_vec())
+        );
+        assert_eq!(
+            engine.decode_lenient("bGlnaHQgd==="),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            engine.decode_lenient("QQ=Q=="),
+            Err(DecodeError::InvalidPadding { index: 2 })
+        );
+    }
+
+    #[test]
+    fn decode_forgiving_works() {
+        let engine = Base64::standard();
+        let config: [&[u8]; 4] = [
+            b"\xEF\xBB\xBF bGlnaHQgdw\r\n",
+            b"\xEF\xBB\xBFbGlnaHQgdw==\n",
+            b"\tbGlnaHQgdw==  ",
+            b"bGlnaHQgdw",
+        ];
+        for input in config {
+            assert_eq!(
+                engine.decode_forgiving(input).as_deref(),
+                Ok(&b"light w"[..])
+            );
+        }
+
+        let err = engine
+            .decode_forgiving(b"\xEF\xBB\xBF bGln aHQgdw\n")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 8,
+                byte: b' '
+            }
+        );
+    }
+}
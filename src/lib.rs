@@ -1,3 +1,61 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod alphabet;
+mod classify;
+mod compare;
+mod config;
+#[cfg(test)]
+mod conformance;
+mod display;
+mod engine;
+mod error;
+mod explain;
+mod framing;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "internals")]
+pub mod internals;
+mod lenient;
+mod macros;
+#[cfg(feature = "rayon")]
+mod par;
+mod pem;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "std")]
+mod read;
+mod state;
+mod transcode;
+mod variant;
+mod verify;
+mod wrap;
+#[cfg(feature = "std")]
+mod write;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+pub use alphabet::Alphabet;
+pub use classify::DecodedChunk;
+pub use config::{AlphabetConfig, EngineConfig};
+pub use engine::{Engine, StandardEngine, UrlSafeEngine};
+pub use error::{
+    AlphabetError, CapacityError, DecodeError, DecodeTryError, EncodeError, UnknownVariant,
+};
+pub use explain::GroupExplain;
+pub use lenient::InvalidAction;
+#[cfg(feature = "std")]
+pub use read::{TerminatedMessages, TranscodeReader};
+pub use state::{DecodeState, EncodeState};
+pub use variant::Variant;
+pub use wrap::LineEnding;
+#[cfg(feature = "std")]
+pub use write::EncoderWriter;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Base64;
 
@@ -18,25 +76,62 @@ impl Base64 {
         b'8', b'9', b'-', b'_',
     ];
 
+    // RFC 3501 modified base64 of mailbox names, `,` replacing `/`
+    const ALPHABET_IMAP: [u8; 64] = {
+        let mut alphabet = Self::ALPHABET_STANDARD;
+        alphabet[63] = b',';
+        alphabet
+    };
+
+    const STANDARD: Base64Engine = Base64Engine::from_parts(Self::ALPHABET_STANDARD, Some(b'='));
+    const URL_SAFE: Base64Engine = Base64Engine::from_parts(Self::ALPHABET_URL_SAFE, Some(b'='));
+    const URL_SAFE_NO_PAD: Base64Engine = Self::URL_SAFE.without_padding();
+    const IMAP: Base64Engine =
+        Base64Engine::from_parts(Self::ALPHABET_IMAP, None).require_canonical(true);
+
     pub const fn standard() -> Base64Engine {
-        Base64Engine {
-            alphabet: &Self::ALPHABET_STANDARD,
-            padding: '=',
-        }
+        Self::STANDARD
     }
 
     pub const fn url_safe() -> Base64Engine {
-        Base64Engine {
-            alphabet: &Self::ALPHABET_URL_SAFE,
-            padding: '=',
-        }
+        Self::URL_SAFE
+    }
+
+    /// Shortcut for `Base64::url_safe().without_padding()`.
+    pub const fn url_safe_no_pad() -> Base64Engine {
+        Self::URL_SAFE_NO_PAD
+    }
+
+    /// Unpadded engine for the modified base64 of RFC 3501 IMAP mailbox names, i.e. the text
+    /// between the `&` and `-` shift chars, encoding UTF-16BE. Only canonical encodings are
+    /// accepted, as required by the RFC.
+    pub const fn imap() -> Base64Engine {
+        Self::IMAP
+    }
+
+    /// Encodes `input` with a one-off `alphabet` and `padding`, validated as by
+    /// [`Base64Engine::new`], without keeping an engine around.
+    pub fn encode_with_alphabet(
+        input: impl AsRef<[u8]>,
+        alphabet: &[u8; 64],
+        padding: Option<u8>,
+    ) -> Result<String, AlphabetError> {
+        let engine = Base64Engine::new(alphabet, padding.map(char::from))?;
+        Ok(engine.encode(input))
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Base64Engine {
-    alphabet: &'static [u8; 64],
-    padding: char,
+    alphabet: [u8; 64],
+    // reverse lookup of `alphabet`, mapping each byte to its 6-bit value or `INVALID_SYMBOL`
+    decode_table: [u8; 256],
+    // always ASCII, so it takes a single byte of the output just like the symbols do
+    padding: Option<u8>,
+    // rejects encodings whose last symbol has non-zero unused bits
+    require_canonical: bool,
+    // reserves the computed output length up front instead of letting the output grow
+    exact_capacity: bool,
 }
 
 impl Base64Engine {
@@ -44,13 +139,306 @@ impl Base64Engine {
     const ENCODE_MASK: u32 = 0x3F;
     // bitmask to get first 8 bits using &(and) operator
     const DECODE_MASK: u32 = 0xFF;
+    // windows are merged big-endian, first byte or symbol in the most significant bits, so the
+    // shifts below extract symbols and bytes in input order
     const ENCODE_RSH: [u8; 4] = [18, 12, 6, 0];
     const DECODE_RSH: [u8; 3] = [16, 8, 0];
+    /// Value of [`Base64Engine::decode_table`] entries for bytes that are not part of the
+    /// alphabet, padding included.
+    pub const INVALID_SYMBOL: u8 = 0xFF;
+
+    /// Builds an engine for a custom `alphabet` of 64 unique ASCII symbols, mapped to the 6-bit
+    /// values by their position, and an optional ASCII `padding` that is not part of the
+    /// alphabet.
+    pub fn new(alphabet: &[u8], padding: Option<char>) -> Result<Self, AlphabetError> {
+        let alphabet = Alphabet::new(alphabet)?;
+        let padding = Self::padding_byte(padding)?;
+        Self::check_padding(&alphabet, padding)?;
+
+        let engine = Self::from_parts(*alphabet.as_bytes(), padding);
+        engine.check_mapping()?;
+        Ok(engine)
+    }
+
+    /// Replaces the alphabet of the engine, keeping its padding and flags. Errors if the
+    /// padding is part of the new alphabet.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Result<Self, AlphabetError> {
+        Self::check_padding(&alphabet, self.padding)?;
+
+        self.alphabet = *alphabet.as_bytes();
+        self.decode_table = Self::build_decode_table(alphabet.as_bytes());
+        Ok(self)
+    }
+
+    /// Replaces the padding of the engine, keeping its alphabet and flags, `None` making it
+    /// encode and decode unpadded base64. Errors if `padding` is not ASCII or is part of the
+    /// alphabet.
+    pub fn with_padding(mut self, padding: Option<char>) -> Result<Self, AlphabetError> {
+        let padding = Self::padding_byte(padding)?;
+        Self::check_padding(&self.alphabet(), padding)?;
+
+        self.padding = padding;
+        Ok(self)
+    }
+
+    /// Same as `with_padding(None)`, which can not fail.
+    pub const fn without_padding(mut self) -> Self {
+        self.padding = None;
+        self
+    }
+
+    /// Returns the reverse lookup table of the alphabet, mapping every byte to its 6-bit value,
+    /// or to [`Base64Engine::INVALID_SYMBOL`] if it is not part of the alphabet.
+    pub const fn decode_table(&self) -> &[u8; 256] {
+        &self.decode_table
+    }
+
+    /// Makes `decode` reject non-canonical encodings, i.e. whose last meaningful symbol carries
+    /// non-zero bits that do not belong to any decoded byte, such as `QR==` instead of `QQ==`.
+    pub const fn require_canonical(mut self, require_canonical: bool) -> Self {
+        self.require_canonical = require_canonical;
+        self
+    }
+
+    /// When `true` (default), reserves the computed length of the output up front, so encoding
+    /// and decoding allocate once. When `false`, the output grows as it is written instead, e.g.
+    /// to observe natural growth while profiling memory. The output is the same either way.
+    pub const fn exact_capacity(mut self, exact_capacity: bool) -> Self {
+        self.exact_capacity = exact_capacity;
+        self
+    }
 
+    /// Encodes `bytes` into a new `String`.
+    ///
+    /// Output bytes are pushed as is rather than as chars, skipping the per-char UTF-8
+    /// bookkeeping of `String::push`, which is expected to speed up large encodes noticeably.
     pub fn encode(&self, bytes: impl AsRef<[u8]>) -> String {
         let bytes = bytes.as_ref();
-        let mut encoded = String::with_capacity(bytes.len() * 4 / 3);
+        let mut encoded = Vec::with_capacity(self.reserved_len(self.encoded_len(bytes.len())));
+        self.encode_each(bytes, |byte| encoded.push(byte));
+        debug_assert_eq!(encoded.len(), self.encoded_len(bytes.len()));
+
+        // SAFETY: alphabet symbols and padding are always ASCII, hence valid UTF-8
+        unsafe { String::from_utf8_unchecked(encoded) }
+    }
+
+    /// Returns the exact length of the unwrapped encoding of `input_len` bytes.
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        let remaining_bytes = input_len % 3;
+        let len = input_len / 3 * 4;
+        match (remaining_bytes, self.padding) {
+            (0, _) => len,
+            (_, Some(_)) => len + 4,
+            // `n` remaining bytes carry enough bits for `n + 1` symbols
+            (n, None) => len + n + 1,
+        }
+    }
+
+    /// Returns how many terminal columns the unwrapped encoding of `input_len` bytes takes.
+    ///
+    /// Encoded output is always ASCII, so its width in chars is its length in bytes, i.e. the
+    /// same as [`Base64Engine::encoded_len`].
+    pub const fn display_width(&self, input_len: usize) -> usize {
+        self.encoded_len(input_len)
+    }
+
+    /// Encodes `bytes` appending the output to `encoded`.
+    pub(crate) fn encode_into(&self, bytes: &[u8], encoded: &mut String) {
+        encoded.reserve(self.reserved_len(self.encoded_len(bytes.len())));
+        self.encode_each(bytes, |byte| encoded.push(char::from(byte)));
+    }
+
+    /// Encodes `bytes` appending the output to `encoded`, reusing its allocation.
+    pub fn encode_append(&self, bytes: impl AsRef<[u8]>, encoded: &mut String) {
+        self.encode_into(bytes.as_ref(), encoded);
+    }
+
+    /// Encodes `bytes` appending the output to `out`, e.g. to embed it in a binary frame,
+    /// returning the newly written region of `out`.
+    pub fn encode_append_bytes<'o>(
+        &self,
+        bytes: impl AsRef<[u8]>,
+        out: &'o mut Vec<u8>,
+    ) -> &'o [u8] {
+        let bytes = bytes.as_ref();
+        let start = out.len();
+        out.reserve(self.reserved_len(self.encoded_len(bytes.len())));
+        self.encode_each(bytes, |byte| out.push(byte));
+
+        &out[start..]
+    }
+
+    /// Encodes the concatenation of `slices`, e.g. scatter-gather buffers, without building
+    /// it, the 0 to 2 bytes of a window split across slices being carried to the next one.
+    pub fn encode_vectored(&self, slices: &[&[u8]]) -> String {
+        let len = slices.iter().map(|slice| slice.len()).sum();
+        let mut encoded = String::with_capacity(self.reserved_len(self.encoded_len(len)));
+
+        let mut state = EncodeState::new(self.clone());
+        for slice in slices {
+            state.push(slice, &mut encoded);
+        }
+        state.finalize(&mut encoded);
+
+        encoded
+    }
+
+    /// Encodes `bytes` into `out`, returning the amount of bytes written. Errors without
+    /// writing anything if `out` is too small to hold the encoded output.
+    pub fn encode_to_slice(
+        &self,
+        bytes: impl AsRef<[u8]>,
+        out: &mut [u8],
+    ) -> Result<usize, EncodeError> {
+        let bytes = bytes.as_ref();
+        let len = self.encoded_len(bytes.len());
+        if len > out.len() {
+            return Err(EncodeError::OutputTooSmall {
+                len,
+                capacity: out.len(),
+            });
+        }
+
+        let mut written = 0;
+        self.encode_each(bytes, |byte| {
+            out[written] = byte;
+            written += 1;
+        });
+        debug_assert_eq!(written, len);
+
+        Ok(written)
+    }
+
+    /// Encodes `bytes` into the stack buffer `buf`, returning the output as a `&str`, e.g. to
+    /// log short tokens without allocating. Fits up to 48 bytes of input.
+    pub fn encode_inline<'b>(
+        &self,
+        bytes: impl AsRef<[u8]>,
+        buf: &'b mut [u8; 64],
+    ) -> Result<&'b str, EncodeError> {
+        let len = self.encode_to_slice(bytes, buf)?;
+        Ok(core::str::from_utf8(&buf[..len]).expect("encoded output should be ASCII"))
+    }
+
+    /// Encodes `bytes` with both the standard and the url-safe alphabets, padded as configured
+    /// by this engine, handy while migrating from one to the other.
+    ///
+    /// The alphabets only differ in their last 2 symbols, so the url-safe encoding is derived
+    /// from the standard one instead of being computed again. Padding that is a symbol of
+    /// either alphabet, e.g. `-` for a standard engine, is replaced with `=` in both.
+    pub fn encode_both(&self, bytes: impl AsRef<[u8]>) -> (String, String) {
+        // padding that is a symbol of either alphabet would be decoded as data by one of them
+        let padding = self.padding.map(|padding| {
+            if Self::is_builtin_symbol(padding) {
+                b'='
+            } else {
+                padding
+            }
+        });
+        let standard = Self {
+            padding,
+            exact_capacity: self.exact_capacity,
+            ..Base64::STANDARD
+        }
+        .encode(bytes);
+
+        // padding only ever trails the symbols, which are the only chars to map
+        let symbols = standard.trim_end_matches(|c| padding == Some(c as u8));
+        let url_safe = symbols
+            .chars()
+            .map(|c| match c {
+                '+' => '-',
+                '/' => '_',
+                c => c,
+            })
+            .chain(standard[symbols.len()..].chars())
+            .collect();
+
+        (standard, url_safe)
+    }
+
+    /// Encodes `bytes` with the url-safe alphabet into a string that never needs
+    /// percent-encoding in any URL component, whatever the alphabet of this engine.
+    ///
+    /// The output is unpadded, unless this engine opted in with a padding that is unreserved
+    /// in URLs but not part of the url-safe alphabet, i.e. `.` or `~`.
+    pub fn encode_url_safe_minimal(&self, bytes: impl AsRef<[u8]>) -> String {
+        let engine = Self {
+            padding: self
+                .padding
+                .filter(|padding| matches!(padding, b'.' | b'~')),
+            exact_capacity: self.exact_capacity,
+            ..Base64::URL_SAFE
+        };
+        let encoded = engine.encode(bytes);
+        debug_assert!(encoded
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~')));
+
+        encoded
+    }
+
+    /// Encodes UTF-16 code units, or any other `u16` values, as their big-endian bytes, i.e.
+    /// the byte order of UTF-16 without a BOM.
+    pub fn encode_utf16(&self, units: &[u16]) -> String {
+        self.encode_units(units, u16::to_be_bytes)
+    }
+
+    /// Same as [`Base64Engine::encode_utf16`], but encoding little-endian bytes, the byte order
+    /// of most UTF-16 coming from Windows.
+    pub fn encode_utf16_le(&self, units: &[u16]) -> String {
+        self.encode_units(units, u16::to_le_bytes)
+    }
+
+    fn encode_units(&self, units: &[u16], to_bytes: fn(u16) -> [u8; 2]) -> String {
+        // 384 units are 768 bytes, a multiple of 3, so only the last chunk may need padding
+        let mut buf = [0u8; 768];
+        let mut encoded =
+            String::with_capacity(self.reserved_len(self.encoded_len(units.len() * 2)));
+        for chunk in units.chunks(buf.len() / 2) {
+            for (bytes, unit) in buf.chunks_exact_mut(2).zip(chunk) {
+                bytes.copy_from_slice(&to_bytes(*unit));
+            }
+            self.encode_into(&buf[..chunk.len() * 2], &mut encoded);
+        }
+
+        encoded
+    }
+
+    /// Encodes a single group of 1 to 3 bytes into up to 4 chars, returning them along with the
+    /// amount of chars to output, padding included. Unused chars of unpadded groups are zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is empty or longer than 3 bytes.
+    pub fn encode_group(&self, bytes: &[u8]) -> ([u8; 4], usize) {
+        assert!(
+            (1..=3).contains(&bytes.len()),
+            "received encoding group with len {}",
+            bytes.len()
+        );
+
+        let mut group = [0u8; 4];
+        let mut len = 0;
+        self.encode_each(bytes, |byte| {
+            group[len] = byte;
+            len += 1;
+        });
+
+        (group, len)
+    }
+
+    /// Returns an iterator over the encoding of `bytes` in groups of 4 chars, e.g. for bulk
+    /// processing in aligned units, the last group padded as configured by this engine.
+    ///
+    /// Without padding, the unused chars of the last group are zero, as with
+    /// [`Base64Engine::encode_group`].
+    pub fn encode_groups<'a>(&'a self, bytes: &'a [u8]) -> impl Iterator<Item = [u8; 4]> + 'a {
+        bytes.chunks(3).map(|chunk| self.encode_group(chunk).0)
+    }
 
+    /// Encodes `bytes` handing every output byte, always ASCII, to `emit`.
+    fn encode_each(&self, bytes: &[u8], mut emit: impl FnMut(u8)) {
         for window in bytes.chunks_exact(3) {
             let merged = match window {
                 [first, second, third] => self.merge_encode_bytes(*first, *second, *third),
@@ -58,100 +446,1938 @@ impl Base64Engine {
                 w => panic!("received encoding window with len {}", w.len()),
             };
 
-            let chars = Self::ENCODE_RSH
+            Self::ENCODE_RSH
                 .into_iter()
-                .map(|rsh| (merged >> rsh) & Self::ENCODE_MASK)
-                .filter(|byte| *byte > 0)
-                .map(|byte| char::from(self.alphabet[byte as usize]));
+                .map(|rsh| self.encode_symbol(merged, rsh))
+                .for_each(&mut emit);
+        }
+
+        // tails are handled with straight-line code, `n` remaining bytes carry enough bits for
+        // `n + 1` symbols
+        match *bytes.chunks_exact(3).remainder() {
+            [first] => {
+                let merged = self.merge_encode_bytes(first, 0, 0);
+                emit(self.encode_symbol(merged, Self::ENCODE_RSH[0]));
+                emit(self.encode_symbol(merged, Self::ENCODE_RSH[1]));
+                if let Some(padding) = self.padding {
+                    emit(padding);
+                    emit(padding);
+                }
+            }
+            [first, second] => {
+                let merged = self.merge_encode_bytes(first, second, 0);
+                emit(self.encode_symbol(merged, Self::ENCODE_RSH[0]));
+                emit(self.encode_symbol(merged, Self::ENCODE_RSH[1]));
+                emit(self.encode_symbol(merged, Self::ENCODE_RSH[2]));
+                if let Some(padding) = self.padding {
+                    emit(padding);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Decodes `encoded`, tolerating a single trailing `\n` or `\r\n`, as found at the end of
+    /// files written by e.g. `echo ... | base64`. Any other line break is an error.
+    ///
+    /// Invalid input that looks like base32, with more padding than base64 ever has and some
+    /// of the `2-7` digits, is reported as [`DecodeError::LikelyBase32`].
+    pub fn decode(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let bytes = encoded.as_ref();
+        let mut decoded = Vec::<u8>::with_capacity(self.reserved_len(self.decoded_len(bytes)));
+        let result = self.decode_each(bytes, |window| decoded.extend_from_slice(window));
+        if result.is_err() && Self::hints_base32(Self::trim_newline(bytes)) {
+            return Err(DecodeError::LikelyBase32);
+        }
+        result?;
+
+        Ok(decoded)
+    }
+
+    /// Decodes `encoded` into `out`, returning the amount of bytes written. Errors without
+    /// writing anything if `out` is too small to hold the decoded output.
+    pub fn decode_to_slice(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        out: &mut [u8],
+    ) -> Result<usize, DecodeError> {
+        let bytes = encoded.as_ref();
+        let len = self.decoded_len(bytes);
+        if len > out.len() {
+            return Err(DecodeError::OutputTooSmall {
+                len,
+                capacity: out.len(),
+            });
+        }
+
+        let mut written = 0;
+        self.decode_each(bytes, |window| {
+            out[written..written + window.len()].copy_from_slice(window);
+            written += window.len();
+        })?;
+
+        Ok(written)
+    }
+
+    /// Same as `decode_to_slice`, but returns the decoded prefix of `scratch` itself, handy to
+    /// decode into a caller provided buffer without allocating.
+    pub fn decode_with_scratch<'s>(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        scratch: &'s mut [u8],
+    ) -> Result<&'s [u8], DecodeError> {
+        let len = self.decode_to_slice(encoded, scratch)?;
+        Ok(&scratch[..len])
+    }
+
+    /// Decodes `encoded` into a stack allocated array, returning it along with the amount of
+    /// bytes used. Errors if the decoded output does not fit in `N` bytes.
+    pub fn decode_to_array<const N: usize>(
+        &self,
+        encoded: impl AsRef<[u8]>,
+    ) -> Result<([u8; N], usize), DecodeError> {
+        let mut decoded = [0u8; N];
+        let len = self.decode_to_slice(encoded, &mut decoded)?;
+
+        Ok((decoded, len))
+    }
+
+    /// Decodes `buf` in place, returning the amount of decoded bytes written at its start.
+    ///
+    /// On error, `buf` is left partially overwritten with the bytes decoded before the error.
+    pub fn decode_in_place(&self, buf: &mut [u8]) -> Result<usize, DecodeError> {
+        let len = Self::trim_newline(buf).len();
+        let buf = &mut buf[..len];
+        // unpadded input is only accepted by engines that do not emit padding
+        if self.padding.is_some() && !buf.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut written = 0;
+        for offset in (0..buf.len()).step_by(4) {
+            let end = buf.len().min(offset + 4);
+            let mut window = [0u8; 4];
+            window[..end - offset].copy_from_slice(&buf[offset..end]);
+
+            let (window_bytes, len) =
+                self.decode_window(&window[..end - offset], offset, end == buf.len())?;
+            // 4 chars decode to at most 3 bytes, so output never catches up with the input
+            buf[written..written + len].copy_from_slice(&window_bytes[..len]);
+            written += len;
+        }
+
+        Ok(written)
+    }
+
+    /// Same as `decode_in_place`, but takes ownership of `input` and returns it truncated to
+    /// the decoded bytes, reusing its allocation.
+    pub fn decode_into_input(&self, mut input: Vec<u8>) -> Result<Vec<u8>, DecodeError> {
+        let len = self.decode_in_place(&mut input)?;
+        input.truncate(len);
+
+        Ok(input)
+    }
+
+    /// Returns the decoded byte at `byte_index` of `encoded`, decoding only the window of 4
+    /// chars holding it, e.g. to seek in large stored encodings.
+    ///
+    /// The rest of `encoded` is not validated.
+    pub fn decode_byte_at(&self, encoded: &[u8], byte_index: usize) -> Result<u8, DecodeError> {
+        // same single trailing line break tolerance as `decode`
+        let encoded = Self::trim_newline(encoded);
+        if self.padding.is_some() && !encoded.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let len = self.decoded_len_trimmed(encoded);
+        if byte_index >= len {
+            return Err(DecodeError::ByteIndexOutOfRange {
+                index: byte_index,
+                len,
+            });
+        }
+
+        let offset = byte_index / 3 * 4;
+        let end = encoded.len().min(offset + 4);
+        let (window_bytes, window_len) =
+            self.decode_window(&encoded[offset..end], offset, end == encoded.len())?;
+
+        // a misplaced padding may make the window shorter than `decoded_len` assumed
+        window_bytes[..window_len]
+            .get(byte_index % 3)
+            .copied()
+            .ok_or(DecodeError::InvalidLength)
+    }
 
-            encoded.extend(chars);
+    /// Returns whether `encoded` is valid base64 that decodes to exactly `expected_bytes` bytes,
+    /// without building the decoded output.
+    pub fn is_valid_len(&self, encoded: impl AsRef<[u8]>, expected_bytes: usize) -> bool {
+        let bytes = encoded.as_ref();
+        if self.decoded_len(bytes) != expected_bytes {
+            return false;
+        }
+
+        let mut len = 0;
+        let valid = self.decode_each(bytes, |window| len += window.len());
+        valid.is_ok() && len == expected_bytes
+    }
+
+    /// Checks that `encoded` decodes without error, canonical form included if required,
+    /// returning it untouched, e.g. for gateways storing base64 as is. Nothing is allocated.
+    pub fn validate_and_return<'a>(&self, encoded: &'a [u8]) -> Result<&'a [u8], DecodeError> {
+        self.decode_each(encoded, |_| {})?;
+        Ok(encoded)
+    }
+
+    /// Decodes `encoded`, erroring unless it decodes to exactly `expected` bytes, e.g. for
+    /// fixed-size keys or nonces.
+    pub fn decode_exact(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        expected: usize,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let decoded = self.decode(encoded)?;
+        if decoded.len() != expected {
+            return Err(DecodeError::UnexpectedLength {
+                got: decoded.len(),
+                expected,
+            });
+        }
+
+        Ok(decoded)
+    }
+
+    /// Decodes `bytes` window by window, handing the decoded bytes of each window to `emit`.
+    pub(crate) fn decode_each(
+        &self,
+        bytes: &[u8],
+        emit: impl FnMut(&[u8]),
+    ) -> Result<(), DecodeError> {
+        self.decode_each_trimmed(Self::trim_newline(bytes), emit)
+    }
+
+    // same as `decode_each`, for input whose trailing line break was already trimmed
+    pub(crate) fn decode_each_trimmed(
+        &self,
+        bytes: &[u8],
+        mut emit: impl FnMut(&[u8]),
+    ) -> Result<(), DecodeError> {
+        // unpadded input is only accepted by engines that do not emit padding
+        if self.padding.is_some() && !bytes.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        for (offset, window) in (0..).step_by(4).zip(bytes.chunks(4)) {
+            // padding may only show up at the end of the last window
+            let is_last = offset + 4 >= bytes.len();
+            let (window_bytes, len) = self.decode_window(window, offset, is_last)?;
+            emit(&window_bytes[..len]);
         }
 
-        let remaining_bytes = bytes.len() % 3;
-        if remaining_bytes != 0 {
-            let merged = match &bytes[bytes.len() - remaining_bytes..] {
-                [first, second] => self.merge_encode_bytes(*first, *second, 0),
-                [first] => self.merge_encode_bytes(*first, 0, 0),
-                _ => self.merge_encode_bytes(0, 0, 0),
+        Ok(())
+    }
+
+    /// Returns an iterator decoding `input` window by window, yielding the decoded bytes of
+    /// each window of 4 chars along with their amount, or the error found in that window.
+    ///
+    /// Unlike `decode`, a malformed window does not stop the iteration, so the windows after it
+    /// are still decoded.
+    pub fn decode_windows<'a>(
+        &'a self,
+        input: &'a [u8],
+    ) -> impl Iterator<Item = Result<([u8; 3], usize), DecodeError>> + 'a {
+        (0..)
+            .step_by(4)
+            .zip(input.chunks(4))
+            .map(move |(offset, window)| {
+                // unpadded windows are only accepted by engines that do not emit padding
+                if self.padding.is_some() && window.len() < 4 {
+                    return Err(DecodeError::InvalidLength);
+                }
+                self.decode_window(window, offset, offset + 4 >= input.len())
+            })
+    }
+
+    /// Decodes a single group of 4 chars into up to 3 bytes, returning them along with the
+    /// amount of valid bytes, i.e. 3 minus the amount of padding chars of `group`.
+    pub fn decode_group(&self, group: [u8; 4]) -> Result<([u8; 3], usize), DecodeError> {
+        self.decode_window(&group, 0, true)
+    }
+
+    // decodes a window of up to 4 chars found at `offset`, which may only be padded if it is
+    // the last one of the input
+    fn decode_window(
+        &self,
+        window: &[u8],
+        offset: usize,
+        is_last: bool,
+    ) -> Result<([u8; 3], usize), DecodeError> {
+        debug_assert!(
+            window.len() <= 4,
+            "received decoding window with len {}",
+            window.len()
+        );
+        let mut merged = 0u32;
+        // bit `i` is set when the `i`-th byte of the window is padding, which never makes it
+        // into `merged`, so it can not be mistaken for the symbol of value 0
+        let mut pad_mask = 0u8;
+
+        for (i, byte) in window.iter().enumerate() {
+            let index = offset + i;
+            let idx = match self.decode_table[usize::from(*byte)] {
+                Self::INVALID_SYMBOL if self.padding == Some(*byte) => {
+                    if !is_last {
+                        return Err(DecodeError::InvalidPadding { index });
+                    }
+                    pad_mask |= 1 << i;
+                    continue;
+                }
+                Self::INVALID_SYMBOL if Self::is_builtin_symbol(*byte) => {
+                    return Err(DecodeError::WrongAlphabet { byte: *byte, index });
+                }
+                Self::INVALID_SYMBOL => {
+                    return Err(DecodeError::InvalidByte { index, byte: *byte });
+                }
+                // a symbol following padding makes the padding itself misplaced
+                _ if pad_mask != 0 => {
+                    return Err(DecodeError::InvalidPadding {
+                        index: offset + pad_mask.trailing_zeros() as usize,
+                    });
+                }
+                idx => u32::from(idx),
             };
+            debug_assert!(idx < 64, "decode table maps {byte:#04x} to {idx}");
 
-            let chars = Self::ENCODE_RSH
-                .into_iter()
-                .map(|rsh| (merged >> rsh) & Self::ENCODE_MASK)
-                .map(|byte| match byte {
-                    0 => self.padding,
-                    byte => char::from(self.alphabet[byte as usize]),
+            let lsh = 6 * (3 - i);
+            merged |= idx << lsh;
+        }
+
+        // padding only ever trails the symbols, so they are the unmasked part of the window
+        let symbols = window.len() - pad_mask.count_ones() as usize;
+        // a window made only of padding carries no data at all
+        if symbols == 0 {
+            return Err(DecodeError::InvalidPadding { index: offset });
+        }
+        // a single symbol carries 6 bits, less than a byte, so no encoding ever ends with one
+        if symbols == 1 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        // each symbol carries 6 bits, so only whole bytes are returned
+        let len = symbols * 6 / 8;
+        let unused_bits = merged & ((1 << (24 - len * 8)) - 1);
+        if self.require_canonical && unused_bits != 0 {
+            return Err(DecodeError::NonCanonical {
+                index: offset + symbols - 1,
+            });
+        }
+
+        debug_assert!(len <= 3);
+        // guaranteed to fit in u8 since we masked with `DECODE_MASK`
+        let window_bytes = Self::DECODE_RSH.map(|rsh| ((merged >> rsh) & Self::DECODE_MASK) as u8);
+        Ok((window_bytes, len))
+    }
+
+    /// Returns whether decoding `input_len` bytes of base64 stays within `budget` bytes.
+    ///
+    /// Padding is not known from the length alone, so the decoded length is assumed to be the
+    /// largest `input_len` can produce.
+    pub const fn will_fit(input_len: usize, budget: usize) -> bool {
+        let decoded_len = input_len / 4 * 3 + input_len % 4 * 3 / 4;
+        decoded_len <= budget
+    }
+
+    /// Same as `decode`, but errors before allocating if the decoded output would take more than
+    /// `budget` bytes.
+    pub fn decode_with_budget(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        budget: usize,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let encoded = encoded.as_ref();
+        let len = self.decoded_len(encoded);
+        if len > budget {
+            return Err(DecodeError::ExceedsBudget { len, budget });
+        }
+
+        self.decode(encoded)
+    }
+
+    /// Decodes base64 from a stream of chars, rejecting any non-ASCII char as an invalid byte.
+    ///
+    /// Error indexes are byte offsets, as if the chars were collected into a `String`.
+    pub fn decode_chars(
+        &self,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let mut encoded = Vec::new();
+        for c in chars {
+            if !c.is_ascii() {
+                let mut utf8 = [0u8; 4];
+                c.encode_utf8(&mut utf8);
+                return Err(DecodeError::InvalidByte {
+                    index: encoded.len(),
+                    byte: utf8[0],
                 });
+            }
+            encoded.push(c as u8);
+        }
+
+        self.decode(encoded)
+    }
+
+    /// Decodes base64 from a fallible source of bytes, e.g. a reader yielding bytes one by
+    /// one, stopping at the first error of the source without collecting the input first.
+    pub fn decode_try_iter<E>(
+        &self,
+        iter: impl IntoIterator<Item = Result<u8, E>>,
+    ) -> Result<Vec<u8>, DecodeTryError<E>> {
+        let mut state = DecodeState::new(self.clone());
+        let mut decoded = Vec::new();
+        let mut buf = [0u8; 256];
+        let mut len = 0;
 
-            encoded.extend(chars);
+        for byte in iter {
+            buf[len] = byte.map_err(DecodeTryError::Source)?;
+            len += 1;
+            if len == buf.len() {
+                state.push(&buf, &mut decoded)?;
+                len = 0;
+            }
         }
 
-        encoded
+        state.push(&buf[..len], &mut decoded)?;
+        state.finalize(&mut decoded)?;
+        Ok(decoded)
+    }
+
+    /// Same as `decode`, but ignores every byte of `input` found in `skip`, e.g. line breaks of
+    /// wrapped input, while any other unexpected byte is still an error.
+    ///
+    /// Error indexes refer to `input`, skipped bytes included.
+    pub fn decode_skipping(
+        &self,
+        input: impl AsRef<[u8]>,
+        skip: &[u8],
+    ) -> Result<Vec<u8>, DecodeError> {
+        let mut skipped = [false; 256];
+        for byte in skip {
+            skipped[usize::from(*byte)] = true;
+        }
+
+        // positions of the kept bytes in `input`, to report errors against it
+        let (positions, kept): (Vec<usize>, Vec<u8>) = input
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| !skipped[usize::from(**byte)])
+            .unzip();
+
+        self.decode(kept)
+            .map_err(|err| err.map_index(|index| positions[index]))
     }
 
-    pub fn decode(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, String> {
+    /// Decodes `encoded`, pairing each decoded byte with the index of the first char it was
+    /// decoded from, e.g. to highlight the source of each byte in a visualizer.
+    ///
+    /// The `n`-th byte of a window starts in its `n`-th char, as bytes are 8 bits and symbols 6.
+    pub fn decode_mapped(
+        &self,
+        encoded: impl AsRef<[u8]>,
+    ) -> Result<Vec<(u8, usize)>, DecodeError> {
         let bytes = encoded.as_ref();
-        let mut decoded = Vec::<u8>::with_capacity(bytes.len() * 3 / 4);
+        let mut mapped = Vec::with_capacity(self.reserved_len(self.decoded_len(bytes)));
+        let mut offset = 0;
+        self.decode_each(bytes, |window| {
+            mapped.extend(
+                window
+                    .iter()
+                    .zip(offset..)
+                    .map(|(byte, index)| (*byte, index)),
+            );
+            offset += 4;
+        })?;
 
-        for window in bytes.chunks_exact(4) {
-            let merged = window.iter().enumerate().try_fold(0, |merged, (i, byte)| {
-                let idx = match self.alphabet.iter().position(|b| b == byte) {
-                    Some(idx) => u32::try_from(idx).ok().unwrap_or_default(),
-                    None if *byte == b'=' => 0u32,
-                    None => return Err(format!("invalid base64 byte: {byte:0x}")),
-                };
+        Ok(mapped)
+    }
 
-                let lsh = 6 * (3 - i);
-                Ok(merged + (idx << lsh))
-            })?;
+    /// Decodes `input` skipping, instead of erroring on, every byte that is not a symbol of the
+    /// alphabet, returning the decoded bytes along with the index and value of each skipped
+    /// byte, e.g. to audit the cleanup of messy data.
+    ///
+    /// Trailing padding is expected and not reported, padding anywhere else is. So is a lone
+    /// last symbol, which carries less than a byte.
+    pub fn decode_report(&self, input: impl AsRef<[u8]>) -> (Vec<u8>, Vec<(usize, u8)>) {
+        let input = input.as_ref();
+        let end = match self.padding {
+            Some(pad) => input
+                .iter()
+                .rposition(|byte| *byte != pad)
+                .map_or(0, |i| i + 1),
+            None => input.len(),
+        };
 
-            for byte in Self::DECODE_RSH
-                .into_iter()
-                // guaranteed to fit in u8 since we masked with `DECODE_MASK`
-                .map(|rsh| ((merged >> rsh) & Self::DECODE_MASK) as u8)
-                .take_while(|byte| *byte > 0)
-            {
-                decoded.push(byte)
+        let mut symbols = Vec::with_capacity(end);
+        let mut skipped = Vec::new();
+        let mut last_symbol = 0;
+        for (index, byte) in input[..end].iter().enumerate() {
+            if self.decode_table[usize::from(*byte)] == Self::INVALID_SYMBOL {
+                skipped.push((index, *byte));
+            } else {
+                symbols.push(*byte);
+                last_symbol = index;
             }
         }
 
-        Ok(decoded)
+        if symbols.len() % 4 == 1 {
+            let byte = symbols.pop().expect("should have a last symbol");
+            let position = skipped.partition_point(|(index, _)| *index < last_symbol);
+            skipped.insert(position, (last_symbol, byte));
+        }
+
+        let decoded = self
+            .clone()
+            .without_padding()
+            .require_canonical(false)
+            .decode(symbols)
+            .expect("should only be left with symbols");
+        (decoded, skipped)
     }
 
-    #[inline(always)]
-    fn merge_encode_bytes(&self, first: u8, second: u8, third: u8) -> u32 {
-        (u32::from(first) << 16) + (u32::from(second) << 8) + u32::from(third)
+    /// Same as `decode`, but also returns how many padding chars, 0 to 2, ended the input.
+    pub fn decode_with_pad_count(
+        &self,
+        encoded: impl AsRef<[u8]>,
+    ) -> Result<(Vec<u8>, u8), DecodeError> {
+        let encoded = encoded.as_ref();
+        let decoded = self.decode(encoded)?;
+
+        // valid input has at most 2 padding chars, all of them trailing
+        let padding = match self.padding {
+            Some(pad) => Self::trim_newline(encoded)
+                .iter()
+                .rev()
+                .take_while(|byte| **byte == pad)
+                .count(),
+            None => 0,
+        };
+
+        Ok((decoded, padding as u8))
     }
-}
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn standard_encode_works() {
-        let config = [
-            ("light w", "bGlnaHQgdw=="),
-            ("light wo", "bGlnaHQgd28="),
-            ("light wor", "bGlnaHQgd29y"),
-        ];
+    /// Decodes `encoded` into an exactly sized boxed slice, without spare capacity.
+    pub fn decode_boxed(&self, encoded: impl AsRef<[u8]>) -> Result<Box<[u8]>, DecodeError> {
+        self.decode(encoded).map(Vec::into_boxed_slice)
+    }
 
-        let engine = Base64::standard();
-        for (input, output) in config {
-            let encoded = engine.encode(input);
-            assert_eq!(&encoded, output);
+    /// Decodes `encoded` into a shared buffer, handy to hand the same decoded blob to many owners.
+    pub fn decode_arc(&self, encoded: impl AsRef<[u8]>) -> Result<Arc<[u8]>, DecodeError> {
+        self.decode(encoded).map(Arc::from)
+    }
+
+    /// Encodes `input` into an array of exactly `N` ASCII bytes, usable in const contexts, see
+    /// [`const_encode!`] to get a `&'static str` out of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics, i.e. fails to compile when const evaluated, if `N` is not the encoded length of
+    /// `input`.
+    pub const fn encode_const<const N: usize>(&self, input: &[u8]) -> [u8; N] {
+        if self.encoded_len(input.len()) != N {
+            panic!("encoded length does not match the output array length");
         }
+
+        let mut encoded = [0u8; N];
+        let (mut i, mut written) = (0, 0);
+        while i < input.len() {
+            let remaining = input.len() - i;
+            let second = if remaining > 1 { input[i + 1] } else { 0 };
+            let third = if remaining > 2 { input[i + 2] } else { 0 };
+            let merged = ((input[i] as u32) << 16) | ((second as u32) << 8) | third as u32;
+
+            // `n` remaining bytes carry enough bits for `n + 1` symbols
+            let symbols = if remaining >= 3 { 4 } else { remaining + 1 };
+            let mut symbol = 0;
+            while symbol < symbols {
+                let rsh = Self::ENCODE_RSH[symbol];
+                encoded[written] = self.alphabet[((merged >> rsh) & Self::ENCODE_MASK) as usize];
+                written += 1;
+                symbol += 1;
+            }
+            i += 3;
+        }
+
+        if let Some(padding) = self.padding {
+            while written < N {
+                encoded[written] = padding;
+                written += 1;
+            }
+        }
+
+        encoded
     }
 
-    #[test]
-    fn standard_decode_works() {
-        let engine = Base64::standard();
-        let inputs = ["Many hands make light wor", "Many hands make light work"];
-        for input in inputs {
-            let encoded = engine.encode(input);
-            let decoded = engine.decode(encoded).expect("should be able to decode");
-            let decoded_string = String::from_utf8(decoded).expect("should be a valid string");
+    /// Encodes an array of `IN` bytes into an array of `OUT` ASCII bytes sized at compile time,
+    /// e.g. for stack buffers of embedded callers.
+    ///
+    /// # Panics
+    ///
+    /// Panics, i.e. fails to compile when const evaluated, if `OUT` is not the encoded length
+    /// of `IN` bytes.
+    pub const fn encode_exact<const IN: usize, const OUT: usize>(
+        &self,
+        input: &[u8; IN],
+    ) -> [u8; OUT] {
+        self.encode_const::<OUT>(input)
+    }
 
-            assert_eq!(decoded_string, input);
+    /// Decodes `encoded` into an array of exactly `N` bytes, usable in const contexts to embed
+    /// decoded fixtures at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics, i.e. fails to compile when const evaluated, if `encoded` is not valid base64 or
+    /// does not decode to exactly `N` bytes.
+    pub const fn decode_const<const N: usize>(&self, encoded: &[u8]) -> [u8; N] {
+        if self.padding.is_some() && !encoded.len().is_multiple_of(4) {
+            panic!("invalid base64 input length");
+        }
+
+        let mut len = encoded.len();
+        if let Some(padding) = self.padding {
+            while len > 0 && encoded.len() - len < 2 && encoded[len - 1] == padding {
+                len -= 1;
+            }
+        }
+
+        if len % 4 == 1 {
+            panic!("invalid base64 input length");
+        }
+        if len * 3 / 4 != N {
+            panic!("decoded length does not match the output array length");
+        }
+
+        let mut decoded = [0u8; N];
+        let mut merged = 0u32;
+        let mut bits = 0;
+        let (mut i, mut written) = (0, 0);
+        while i < len {
+            let idx = self.decode_table[encoded[i] as usize];
+            if idx == Self::INVALID_SYMBOL {
+                panic!("invalid base64 byte");
+            }
+
+            merged = (merged << 6) | idx as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                decoded[written] = ((merged >> bits) & Self::DECODE_MASK) as u8;
+                written += 1;
+            }
+            i += 1;
+        }
+
+        decoded
+    }
+
+    // strips the single trailing line break tolerated by decoding, as left by e.g. the output
+    // of `base64` piped through a file
+    fn trim_newline(encoded: &[u8]) -> &[u8] {
+        encoded
+            .strip_suffix(b"\r\n")
+            .or_else(|| encoded.strip_suffix(b"\n"))
+            .unwrap_or(encoded)
+    }
+
+    // length `encoded` decodes to, assuming it is valid
+    fn decoded_len(&self, encoded: &[u8]) -> usize {
+        self.decoded_len_trimmed(Self::trim_newline(encoded))
+    }
+
+    // same as `decoded_len`, for input whose trailing line break was already trimmed
+    fn decoded_len_trimmed(&self, encoded: &[u8]) -> usize {
+        let padding = match self.padding {
+            Some(pad) => encoded
+                .iter()
+                .rev()
+                .take(2)
+                .take_while(|byte| **byte == pad)
+                .count(),
+            None => 0,
+        };
+
+        Self::decoded_len_from_parts(encoded.len(), padding as u8)
+    }
+
+    /// Returns the length that `encoded_len` bytes of valid base64 ending with `pad_count`
+    /// padding chars decode to, e.g. for headers storing both instead of the payload size.
+    pub const fn decoded_len_from_parts(encoded_len: usize, pad_count: u8) -> usize {
+        let len = encoded_len.saturating_sub(pad_count as usize);
+        len / 4 * 3 + len % 4 * 3 / 4
+    }
+
+    /// Checks that the engine is usable: its alphabet is made of 64 unique ASCII symbols, its
+    /// decode table maps each of them back to its own 6-bit value and nothing else, and its
+    /// padding, if any, is not one of them. Engines built through the public constructors are
+    /// always valid, this is meant for a check of every configured engine upfront.
+    pub fn validate(&self) -> Result<(), AlphabetError> {
+        let alphabet = Alphabet::new(&self.alphabet)?;
+        self.check_mapping()?;
+        Self::check_padding(&alphabet, self.padding)
+    }
+
+    // every 6-bit value must be decoded from exactly one byte, the symbol encoding it
+    fn check_mapping(&self) -> Result<(), AlphabetError> {
+        let mut mapped = [0usize; 64];
+        for value in self.decode_table {
+            if value == Self::INVALID_SYMBOL {
+                continue;
+            }
+            match mapped.get_mut(usize::from(value)) {
+                Some(count) => *count += 1,
+                None => return Err(AlphabetError::IncompleteMapping { value }),
+            }
         }
+
+        for (value, symbol) in (0..).zip(self.alphabet) {
+            let decoded = self.decode_table[usize::from(symbol)];
+            if mapped[usize::from(value)] != 1 || decoded != value {
+                return Err(AlphabetError::IncompleteMapping { value });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub const fn alphabet(&self) -> Alphabet {
+        Alphabet::from_array(self.alphabet)
+    }
+
+    /// Returns a fingerprint of the configuration of the engine, its alphabet, padding and
+    /// flags, e.g. to tag encoded output in caches or logs.
+    ///
+    /// It is the 64-bit FNV-1a hash of the alphabet, followed by `[1, padding]`, or `[0, 0]`
+    /// without padding, and by `require_canonical` as a byte, so it is stable across runs,
+    /// platforms and releases. `exact_capacity` is left out, as it never changes the output.
+    pub fn config_fingerprint(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let padding = match self.padding {
+            Some(padding) => [1, padding],
+            None => [0, 0],
+        };
+        self.alphabet
+            .iter()
+            .chain(&padding)
+            .chain(&[u8::from(self.require_canonical)])
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Replaces the `+` symbol with `-`, keeping `/`, a variant used by some systems that is
+    /// neither standard nor url-safe.
+    pub fn minus_for_plus(self) -> Result<Self, AlphabetError> {
+        let alphabet = self.alphabet().swap(b'+', b'-')?;
+        self.with_alphabet(alphabet)
+    }
+
+    // base32 made only of letters is too close to malformed base64 to tell them apart
+    fn hints_base32(bytes: &[u8]) -> bool {
+        Base64::looks_like_base32(bytes)
+            && bytes.ends_with(b"===")
+            && bytes.iter().any(|byte| matches!(byte, b'2'..=b'7'))
+    }
+
+    // how much of the computed output length `len` to reserve up front
+    fn reserved_len(&self, len: usize) -> usize {
+        if self.exact_capacity {
+            len
+        } else {
+            0
+        }
+    }
+
+    // narrows `padding` to the single ASCII byte it is stored as
+    fn padding_byte(padding: Option<char>) -> Result<Option<u8>, AlphabetError> {
+        match padding {
+            Some(padding) if !padding.is_ascii() => Err(AlphabetError::NonAsciiPadding { padding }),
+            padding => Ok(padding.map(|padding| padding as u8)),
+        }
+    }
+
+    fn check_padding(alphabet: &Alphabet, padding: Option<u8>) -> Result<(), AlphabetError> {
+        match padding {
+            Some(byte) if alphabet.as_bytes().contains(&byte) => {
+                Err(AlphabetError::PaddingInAlphabet { byte })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    const fn from_parts(alphabet: [u8; 64], padding: Option<u8>) -> Self {
+        Self {
+            decode_table: Self::build_decode_table(&alphabet),
+            alphabet,
+            padding,
+            require_canonical: false,
+            exact_capacity: true,
+        }
+    }
+
+    const fn build_decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
+        let mut table = [Self::INVALID_SYMBOL; 256];
+        let mut idx = 0;
+        while idx < alphabet.len() {
+            table[alphabet[idx] as usize] = idx as u8;
+            idx += 1;
+        }
+
+        table
+    }
+
+    // whether `byte` is a symbol of the standard or url-safe alphabet, to hint at input
+    // encoded with the other variant
+    fn is_builtin_symbol(byte: u8) -> bool {
+        let byte = usize::from(byte);
+        Base64::STANDARD.decode_table[byte] != Self::INVALID_SYMBOL
+            || Base64::URL_SAFE.decode_table[byte] != Self::INVALID_SYMBOL
+    }
+
+    #[inline(always)]
+    fn encode_symbol(&self, merged: u32, rsh: u8) -> u8 {
+        debug_assert!(
+            merged < 1 << 24,
+            "merged window {merged:#x} wider than 3 bytes"
+        );
+        let idx = ((merged >> rsh) & Self::ENCODE_MASK) as usize;
+        debug_assert!(idx < self.alphabet.len());
+        self.alphabet[idx]
+    }
+
+    #[inline(always)]
+    fn merge_encode_bytes(&self, first: u8, second: u8, third: u8) -> u32 {
+        (u32::from(first) << 16) + (u32::from(second) << 8) + u32::from(third)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_encode_works() {
+        let config = [
+            ("light w", "bGlnaHQgdw=="),
+            ("light wo", "bGlnaHQgd28="),
+            ("light wor", "bGlnaHQgd29y"),
+        ];
+
+        let engine = Base64::standard();
+        for (input, output) in config {
+            let encoded = engine.encode(input);
+            assert_eq!(&encoded, output);
+        }
+    }
+
+    #[test]
+    fn encode_matches_char_by_char_encoding() {
+        let input = (0..(1 << 20) + 2)
+            .map(|i: u32| (i * 31 % 251) as u8)
+            .collect::<Vec<u8>>();
+
+        for engine in [Base64::standard(), Base64::url_safe_no_pad()] {
+            let mut expected = String::new();
+            engine.encode_into(&input, &mut expected);
+            assert_eq!(engine.encode(&input), expected);
+        }
+    }
+
+    #[test]
+    fn standard_decode_works() {
+        let engine = Base64::standard();
+        let inputs = ["Many hands make light wor", "Many hands make light work"];
+        for input in inputs {
+            let encoded = engine.encode(input);
+            let decoded = engine.decode(encoded).expect("should be able to decode");
+            let decoded_string = String::from_utf8(decoded).expect("should be a valid string");
+
+            assert_eq!(decoded_string, input);
+        }
+    }
+
+    #[test]
+    fn encode_tail_works() {
+        let engines = [Base64::standard(), Base64::url_safe_no_pad()];
+        let prefix = b"Many hands make light wor";
+        let one_byte = (0..=u8::MAX).map(|byte| vec![byte]);
+        let two_bytes = (0..=u16::MAX).map(|bytes| bytes.to_be_bytes().to_vec());
+        let tails = core::iter::once(vec![]).chain(one_byte).chain(two_bytes);
+
+        for tail in tails {
+            for engine in &engines {
+                let encoded = engine.encode(&tail);
+                assert_eq!(encoded.len(), engine.encoded_len(tail.len()));
+
+                let canonical = engine.clone().require_canonical(true);
+                assert_eq!(canonical.decode(&encoded).as_ref(), Ok(&tail));
+
+                let input = [&prefix[..24], &tail].concat();
+                let expected = engine.encode(&prefix[..24]) + &encoded;
+                assert_eq!(engine.encode(input), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn display_width_matches_encoded_len() {
+        for engine in [Base64::standard(), Base64::url_safe_no_pad()] {
+            for input_len in 0..32 {
+                let width = engine.display_width(input_len);
+                assert_eq!(width, engine.encoded_len(input_len));
+                assert_eq!(width, engine.encode(vec![0xff; input_len]).chars().count());
+            }
+        }
+    }
+
+    #[test]
+    fn encode_exact_works() {
+        let engine = Base64::standard();
+        let encoded: [u8; 4] = engine.encode_exact(b"ABC");
+        assert_eq!(&encoded, b"QUJD");
+        let encoded: [u8; 8] = engine.encode_exact(&[0xfb, 0xff, 0xbf, 0xfe]);
+        assert_eq!(&encoded, b"+/+//g==");
+
+        const ENCODED: [u8; 6] = Base64::url_safe_no_pad().encode_exact(&[0xfb, 0xff, 0xbf, 0xfe]);
+        assert_eq!(&ENCODED, b"-_-__g");
+    }
+
+    #[test]
+    fn encode_groups_works() {
+        let input = b"light work";
+        let engine = Base64::standard();
+        let groups = engine.encode_groups(input).collect::<Vec<[u8; 4]>>();
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups.concat(), engine.encode(input).as_bytes());
+
+        let engine = Base64::url_safe_no_pad();
+        let groups = engine.encode_groups(input).collect::<Vec<[u8; 4]>>();
+        assert_eq!(groups.last(), Some(&[b'a', b'w', 0, 0]));
+        let flattened = groups.concat();
+        assert_eq!(&flattened[..14], engine.encode(input).as_bytes());
+
+        assert_eq!(engine.encode_groups(b"").count(), 0);
+    }
+
+    #[test]
+    fn encode_group_works() {
+        let engine = Base64::standard();
+        assert_eq!(engine.encode_group(b"A"), (*b"QQ==", 4));
+        assert_eq!(engine.encode_group(b"AB"), (*b"QUI=", 4));
+        assert_eq!(engine.encode_group(b"ABC"), (*b"QUJD", 4));
+
+        let engine = Base64::url_safe_no_pad();
+        assert_eq!(engine.encode_group(b"A"), ([b'Q', b'Q', 0, 0], 2));
+        assert_eq!(engine.encode_group(b"AB"), ([b'Q', b'U', b'I', 0], 3));
+        assert_eq!(engine.encode_group(b"ABC"), (*b"QUJD", 4));
+    }
+
+    #[test]
+    fn encode_append_works() {
+        let engine = Base64::standard();
+        let mut encoded = String::from("data:");
+        engine.encode_append("light w", &mut encoded);
+        assert_eq!(encoded, "data:bGlnaHQgdw==");
+
+        let mut out = Vec::from(&b"\x00\x0c"[..]);
+        let appended = engine.encode_append_bytes("light w", &mut out);
+        assert_eq!(appended, engine.encode("light w").as_bytes());
+        assert_eq!(out, b"\x00\x0cbGlnaHQgdw==");
+    }
+
+    #[test]
+    fn validate_and_return_works() {
+        let engine = Base64::standard().require_canonical(true);
+        let encoded = b"bGlnaHQgdw==";
+        assert_eq!(engine.validate_and_return(encoded), Ok(&encoded[..]));
+
+        let config: [(&[u8], DecodeError); 5] = [
+            (
+                b"bGln*HQg",
+                DecodeError::InvalidByte {
+                    index: 4,
+                    byte: b'*',
+                },
+            ),
+            (
+                b"bGln-HQg",
+                DecodeError::WrongAlphabet {
+                    byte: b'-',
+                    index: 4,
+                },
+            ),
+            (b"bGlnaHQgdw", DecodeError::InvalidLength),
+            (b"bG==aHQg", DecodeError::InvalidPadding { index: 2 }),
+            (b"bGlnaHQgdx==", DecodeError::NonCanonical { index: 9 }),
+        ];
+        for (input, err) in config {
+            assert_eq!(engine.validate_and_return(input), Err(err));
+        }
+    }
+
+    #[test]
+    fn decode_hints_at_base32() {
+        let engine = Base64::standard();
+        assert_eq!(
+            engine.decode("MZXW6YTBOI======"),
+            Err(DecodeError::LikelyBase32)
+        );
+        assert_eq!(
+            engine.decode("NRUWO2DUEB3W64TLEA======"),
+            Err(DecodeError::LikelyBase32)
+        );
+        assert_eq!(
+            engine.decode("MZXQ===="),
+            Err(DecodeError::InvalidPadding { index: 4 })
+        );
+        assert_eq!(
+            engine.decode("MZXW6YTBOI=====A"),
+            Err(DecodeError::InvalidPadding { index: 10 })
+        );
+    }
+
+    #[test]
+    fn decode_rejects_lone_last_symbol() {
+        let engine = Base64::url_safe_no_pad();
+        assert_eq!(engine.decode("bGlna"), Err(DecodeError::InvalidLength));
+        assert_eq!(engine.decode("bGlnaHQgd"), Err(DecodeError::InvalidLength));
+        assert_eq!(engine.decode("bGlnaHQgdw"), Ok(b"light w".to_vec()));
+
+        let engine = Base64::standard();
+        assert_eq!(
+            engine.decode("bGlnaHQgd==="),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            engine.decode_lenient("bGlna"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            engine.decode_lenient("bGlnaHQgd="),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn decode_reports_absolute_offsets_in_large_input() {
+        let engine = Base64::standard();
+        let input = (0..3 * 1024 * 1024u32)
+            .map(|i| (i % 253) as u8)
+            .collect::<Vec<u8>>();
+        // stands for a read-only view of a memory-mapped file
+        let mut mapped = engine.encode(&input).into_bytes().into_boxed_slice();
+        let view: &[u8] = &mapped;
+        assert_eq!(engine.decode(view).as_deref(), Ok(&input[..]));
+
+        let index = mapped.len() - 7;
+        mapped[index] = b'*';
+        let err = DecodeError::InvalidByte { index, byte: b'*' };
+        assert_eq!(engine.decode(&mapped[..]), Err(err.clone()));
+        assert_eq!(
+            engine.decode_to_slice(&mapped[..], &mut vec![0; input.len()]),
+            Err(err)
+        );
+    }
+
+    #[test]
+    fn decode_exact_works() {
+        let engine = Base64::standard();
+        let key = engine.encode([7u8; 16]);
+        assert_eq!(engine.decode_exact(&key, 16), Ok(vec![7u8; 16]));
+        assert_eq!(
+            engine.decode_exact(&key, 17),
+            Err(DecodeError::UnexpectedLength {
+                got: 16,
+                expected: 17
+            })
+        );
+    }
+
+    #[test]
+    fn encode_with_alphabet_works() {
+        let mut alphabet = Base64::ALPHABET_STANDARD;
+        alphabet.reverse();
+        let encoded = Base64::encode_with_alphabet("light w", &alphabet, Some(b'.'));
+        assert_eq!(encoded.as_deref(), Ok("k5aYl4vfiP.."));
+
+        let engine = Base64Engine::new(&alphabet, Some('.')).expect("valid alphabet");
+        assert_eq!(
+            engine.decode("k5aYl4vfiP..").as_deref(),
+            Ok(&b"light w"[..])
+        );
+
+        assert_eq!(
+            Base64::encode_with_alphabet("light w", &alphabet, Some(b'A')),
+            Err(AlphabetError::PaddingInAlphabet { byte: b'A' })
+        );
+        assert_eq!(
+            Base64::encode_with_alphabet("light w", &alphabet, Some(0xe9)),
+            Err(AlphabetError::NonAsciiPadding { padding: 'é' })
+        );
+    }
+
+    #[test]
+    fn encode_utf16_works() {
+        let engine = Base64::standard();
+        let units = "日本語 light".encode_utf16().collect::<Vec<u16>>();
+        let be = units
+            .iter()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect::<Vec<u8>>();
+        let le = units
+            .iter()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect::<Vec<u8>>();
+
+        let encoded = engine.encode_utf16(&units);
+        assert_eq!(encoded, "ZeVnLIqeACAAbABpAGcAaAB0");
+        assert_eq!(engine.decode(&encoded), Ok(be));
+        assert_eq!(engine.decode(engine.encode_utf16_le(&units)), Ok(le));
+
+        let long = vec![0xabcd_u16; 1000];
+        let bytes = long
+            .iter()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect::<Vec<u8>>();
+        assert_eq!(engine.encode_utf16(&long), engine.encode(bytes));
+    }
+
+    #[test]
+    fn encode_url_safe_minimal_works() {
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+        for engine in [Base64::standard(), Base64::url_safe(), Base64::imap()] {
+            let encoded = engine.encode_url_safe_minimal(input);
+            assert_eq!(encoded, "-_-__g");
+            assert!(!encoded.contains(['+', '/', '=']));
+            assert!(encoded
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'));
+        }
+
+        let dotted = Base64::standard()
+            .with_padding(Some('.'))
+            .expect("valid padding");
+        assert_eq!(dotted.encode_url_safe_minimal(input), "-_-__g..");
+    }
+
+    #[test]
+    fn encode_vectored_works() {
+        let input = (0..=255u8).collect::<Vec<u8>>();
+        let config: [&[usize]; 5] = [&[], &[0], &[1, 2], &[2, 5, 7, 100], &[1, 2, 3, 4, 128]];
+
+        for engine in [Base64::standard(), Base64::url_safe_no_pad()] {
+            for splits in config {
+                let mut slices = Vec::new();
+                let mut rest = &input[..];
+                let mut start = 0;
+                for split in splits {
+                    let (slice, tail) = rest.split_at(split - start);
+                    slices.push(slice);
+                    rest = tail;
+                    start = *split;
+                }
+                slices.push(rest);
+
+                assert_eq!(engine.encode_vectored(&slices), engine.encode(&input));
+                assert_eq!(slices.concat(), input);
+            }
+        }
+
+        assert_eq!(Base64::standard().encode_vectored(&[]), "");
+    }
+
+    #[test]
+    fn encode_inline_works() {
+        let engine = Base64::url_safe_no_pad();
+        let mut buf = [0u8; 64];
+        let token = engine.encode_inline([0xfb; 16], &mut buf);
+        assert_eq!(token, Ok("-_v7-_v7-_v7-_v7-_v7-w"));
+        assert_eq!(token.map(str::len), Ok(engine.encoded_len(16)));
+
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            Base64::standard()
+                .encode_inline([0; 48], &mut buf)
+                .map(str::len),
+            Ok(64)
+        );
+        assert_eq!(
+            Base64::standard().encode_inline([0; 49], &mut buf),
+            Err(EncodeError::OutputTooSmall {
+                len: 68,
+                capacity: 64
+            })
+        );
+    }
+
+    #[test]
+    fn exact_capacity_does_not_change_output() {
+        let input = (0..1000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let exact = Base64::standard();
+        let growing = Base64::standard().exact_capacity(false);
+
+        for len in [0, 1, 2, 3, 100, 1000] {
+            let encoded = exact.encode(&input[..len]);
+            assert_eq!(encoded.capacity(), encoded.len());
+            assert_eq!(growing.encode(&input[..len]), encoded);
+
+            let decoded = growing.decode(&encoded);
+            assert_eq!(decoded, exact.decode(&encoded));
+            assert_eq!(decoded.as_deref(), Ok(&input[..len]));
+
+            let mut out = Vec::new();
+            growing.encode_append_bytes(&input[..len], &mut out);
+            assert_eq!(out, encoded.as_bytes());
+
+            let (head, tail) = input[..len].split_at(len / 2);
+            assert_eq!(growing.encode_vectored(&[head, tail]), encoded);
+            let vectored = exact.encode_vectored(&[head, tail]);
+            assert_eq!(vectored.capacity(), vectored.len());
+
+            let units = input[..len]
+                .iter()
+                .map(|byte| u16::from(*byte))
+                .collect::<Vec<u16>>();
+            assert_eq!(growing.encode_utf16(&units), exact.encode_utf16(&units));
+            assert_eq!(
+                growing.encode_utf16_le(&units),
+                exact.encode_utf16_le(&units)
+            );
+
+            assert_eq!(
+                growing.encode_both(&input[..len]),
+                exact.encode_both(&input[..len])
+            );
+            assert_eq!(
+                growing.encode_wrapped(&input[..len], 76, LineEnding::Lf),
+                exact.encode_wrapped(&input[..len], 76, LineEnding::Lf)
+            );
+            assert_eq!(
+                growing.transcode(&encoded, &Base64::url_safe()),
+                exact.transcode(&encoded, &Base64::url_safe())
+            );
+        }
+    }
+
+    #[test]
+    fn encode_both_works() {
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+
+        let (standard, url_safe) = Base64::standard().encode_both(input);
+        assert_eq!(standard, Base64::standard().encode(input));
+        assert_eq!(url_safe, Base64::url_safe().encode(input));
+
+        let (standard, url_safe) = Base64::url_safe_no_pad().encode_both(input);
+        assert_eq!(standard, "+/+//g");
+        assert_eq!(url_safe, Base64::url_safe_no_pad().encode(input));
+
+        let dotted = Base64::standard()
+            .with_padding(Some('.'))
+            .expect("valid padding");
+        let (standard, url_safe) = dotted.encode_both([0xff]);
+        assert_eq!((standard.as_str(), url_safe.as_str()), ("/w..", "_w.."));
+
+        let plus = Base64::url_safe()
+            .with_padding(Some('+'))
+            .expect("valid padding");
+        let (standard, url_safe) = plus.encode_both([0]);
+        assert_eq!((standard.as_str(), url_safe.as_str()), ("AA==", "AA=="));
+        assert_eq!(Base64::standard().decode(&standard), Ok(vec![0]));
+    }
+
+    #[test]
+    fn imap_decode_works() {
+        let engine = Base64::imap();
+        let mailboxes = [
+            ("ZeVnLIqe", "日本語"),
+            ("AOk", "é"),
+            ("ZeVnLIqe,,8", "日本語\u{ffff}"),
+        ];
+
+        for (encoded, mailbox) in mailboxes {
+            let utf16 = mailbox
+                .encode_utf16()
+                .flat_map(u16::to_be_bytes)
+                .collect::<Vec<u8>>();
+            assert_eq!(engine.decode(encoded), Ok(utf16.clone()));
+            assert_eq!(engine.encode(&utf16), encoded);
+        }
+
+        assert_eq!(
+            engine.decode("AOl"),
+            Err(DecodeError::NonCanonical { index: 2 })
+        );
+        assert_eq!(
+            engine.decode("ZeVn/Iqe"),
+            Err(DecodeError::WrongAlphabet {
+                byte: b'/',
+                index: 4
+            })
+        );
+    }
+
+    #[test]
+    fn with_padding_works() {
+        let input = [0xfb, 0xff];
+
+        let unpadded = Base64::url_safe().without_padding();
+        assert_eq!(unpadded, Base64::url_safe_no_pad());
+        assert_eq!(unpadded.encode(input), "-_8");
+        assert_eq!(unpadded.decode("-_8=").ok(), None);
+
+        let padded = Base64::url_safe_no_pad()
+            .with_padding(Some('='))
+            .expect("should accept padding");
+        assert_eq!(padded, Base64::url_safe());
+        assert_eq!(padded.encode(input), "-_8=");
+
+        let dotted = Base64::url_safe()
+            .with_padding(Some('.'))
+            .expect("should accept padding");
+        assert_eq!(dotted.encode(input), "-_8.");
+        assert_eq!(dotted.decode("-_8.").as_deref(), Ok(&input[..]));
+
+        let err = Base64::url_safe().with_padding(Some('_')).unwrap_err();
+        assert_eq!(err, AlphabetError::PaddingInAlphabet { byte: b'_' });
+    }
+
+    #[test]
+    fn custom_engine_works() {
+        let mut alphabet = Base64::ALPHABET_STANDARD;
+        alphabet.reverse();
+        let engine = Base64Engine::new(&alphabet, Some('=')).expect("should be a valid alphabet");
+
+        let input = b"Many hands make light work";
+        let encoded = engine.encode(input);
+        assert_eq!(encoded, "sp6Rht+XnpGbjN+SnpSa35OWmJeL34iQjZT=");
+        assert_eq!(engine.decode(&encoded).as_deref(), Ok(&input[..]));
+
+        let err = Base64Engine::new(&alphabet[..63], Some('=')).unwrap_err();
+        assert_eq!(err, AlphabetError::InvalidLength { len: 63 });
+
+        alphabet[1] = b'/';
+        let err = Base64Engine::new(&alphabet, Some('=')).unwrap_err();
+        assert_eq!(err, AlphabetError::DuplicateSymbol { byte: b'/' });
+
+        let err = Base64Engine::new(&Base64::ALPHABET_STANDARD, Some('A')).unwrap_err();
+        assert_eq!(err, AlphabetError::PaddingInAlphabet { byte: b'A' });
+    }
+
+    #[test]
+    fn custom_engine_rejects_non_ascii_padding() {
+        let err = Base64Engine::new(&Base64::ALPHABET_STANDARD, Some('¤')).unwrap_err();
+        assert_eq!(err, AlphabetError::NonAsciiPadding { padding: '¤' });
+
+        let engine = Base64Engine::new(&Base64::ALPHABET_STANDARD, Some('.'))
+            .expect("should accept ASCII padding");
+        assert_eq!(engine.encode("A"), "QQ..");
+        assert_eq!(engine.decode("QQ..").as_deref(), Ok(&b"A"[..]));
+    }
+
+    #[test]
+    fn validate_works() {
+        for engine in [
+            Base64::standard(),
+            Base64::url_safe(),
+            Base64::url_safe_no_pad(),
+            Base64::imap(),
+        ] {
+            assert_eq!(engine.validate(), Ok(()));
+        }
+
+        let mut alphabet = Base64::ALPHABET_STANDARD;
+        alphabet[63] = b'+';
+        let engine = Base64Engine::from_parts(alphabet, Some(b'='));
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::DuplicateSymbol { byte: b'+' });
+
+        alphabet[63] = 0xC3;
+        let engine = Base64Engine::from_parts(alphabet, Some(b'='));
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::NonAsciiSymbol { byte: 0xC3 });
+
+        let engine = Base64Engine::from_parts(Base64::ALPHABET_STANDARD, Some(b'/'));
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::PaddingInAlphabet { byte: b'/' });
+    }
+
+    #[test]
+    fn validate_rejects_incomplete_mapping() {
+        let mut engine = Base64::standard();
+        engine.decode_table[usize::from(b'B')] = Base64Engine::INVALID_SYMBOL;
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::IncompleteMapping { value: 1 });
+
+        let mut engine = Base64::standard();
+        engine.decode_table[usize::from(b'*')] = 5;
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::IncompleteMapping { value: 5 });
+
+        let mut engine = Base64::standard();
+        engine.decode_table[usize::from(b'*')] = 64;
+        let err = engine.validate().unwrap_err();
+        assert_eq!(err, AlphabetError::IncompleteMapping { value: 64 });
+
+        let err = Base64Engine::new(&Base64::ALPHABET_STANDARD[1..], Some('=')).unwrap_err();
+        assert_eq!(err, AlphabetError::InvalidLength { len: 63 });
+    }
+
+    #[test]
+    fn config_fingerprint_works() {
+        let standard = Base64::standard();
+        assert_eq!(standard.config_fingerprint(), 0x3d4c_5a25_5c8a_ccce);
+        assert_eq!(
+            standard.config_fingerprint(),
+            Base64Engine::new(&Base64::ALPHABET_STANDARD, Some('='))
+                .expect("valid alphabet")
+                .config_fingerprint()
+        );
+
+        let others = [
+            Base64::url_safe(),
+            Base64::standard().without_padding(),
+            Base64::standard().require_canonical(true),
+            Base64::imap(),
+        ];
+        for engine in others {
+            assert_ne!(engine, standard);
+            assert_ne!(engine.config_fingerprint(), standard.config_fingerprint());
+        }
+
+        let growing = Base64::standard().exact_capacity(false);
+        assert_eq!(growing.config_fingerprint(), standard.config_fingerprint());
+    }
+
+    #[test]
+    fn minus_for_plus_works() {
+        let engine = Base64::standard()
+            .minus_for_plus()
+            .expect("should replace the plus symbol");
+
+        // 0xfb 0xff encodes to the 62 and 63 symbols
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+        let encoded = engine.encode(input);
+        assert_eq!(encoded, "-/-//g==");
+        assert_eq!(engine.decode(&encoded), Ok(input.to_vec()));
+
+        let err = Base64::url_safe().minus_for_plus().unwrap_err();
+        assert_eq!(err, AlphabetError::MissingSymbol { byte: b'+' });
+    }
+
+    #[test]
+    fn decode_table_works() {
+        let engine = Base64::standard();
+        let table = engine.decode_table();
+
+        assert_eq!(table[usize::from(b'A')], 0);
+        assert_eq!(table[usize::from(b'/')], 63);
+        assert_eq!(table[usize::from(b'=')], Base64Engine::INVALID_SYMBOL);
+        assert_eq!(table[usize::from(b'-')], Base64Engine::INVALID_SYMBOL);
+
+        let symbols = table
+            .iter()
+            .filter(|idx| **idx != Base64Engine::INVALID_SYMBOL);
+        assert_eq!(symbols.count(), 64);
+    }
+
+    #[test]
+    fn encode_to_slice_works() {
+        let engine = Base64::standard();
+        let mut out = [0u8; 8];
+
+        let written = engine.encode_to_slice(b"light", &mut out);
+        assert_eq!(written, Ok(8));
+        assert_eq!(&out, b"bGlnaHQ=");
+
+        let err = engine.encode_to_slice(b"light", &mut out[..7]).unwrap_err();
+        assert_eq!(
+            err,
+            EncodeError::OutputTooSmall {
+                len: 8,
+                capacity: 7
+            }
+        );
+    }
+
+    #[test]
+    fn decode_into_input_reuses_allocation() {
+        let engine = Base64::standard();
+        let input = (0..1000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoded = engine.encode(&input).into_bytes();
+        let (ptr, capacity) = (encoded.as_ptr(), encoded.capacity());
+
+        let decoded = engine
+            .decode_into_input(encoded)
+            .expect("should decode in place");
+        assert_eq!(decoded, input);
+        assert_eq!((decoded.as_ptr(), decoded.capacity()), (ptr, capacity));
+
+        let mut buf = *b"bGlnaHQgd28=";
+        assert_eq!(engine.decode_in_place(&mut buf), Ok(8));
+        assert_eq!(&buf[..8], b"light wo");
+
+        let mut buf = Base64::url_safe_no_pad().encode("light w").into_bytes();
+        assert_eq!(Base64::url_safe_no_pad().decode_in_place(&mut buf), Ok(7));
+        assert_eq!(
+            engine.decode_into_input(b"bG*n".to_vec()),
+            Err(DecodeError::InvalidByte {
+                index: 2,
+                byte: b'*'
+            })
+        );
+    }
+
+    #[test]
+    fn decode_byte_at_works() {
+        let engine = Base64::standard();
+        let input = (0..1000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoded = engine.encode(&input[..998]);
+        let decoded = engine.decode(&encoded).expect("should decode");
+
+        for byte_index in [0, 1, 2, 500, 996, 997] {
+            let byte = engine.decode_byte_at(encoded.as_bytes(), byte_index);
+            assert_eq!(byte, Ok(decoded[byte_index]));
+        }
+        assert_eq!(
+            engine.decode_byte_at(encoded.as_bytes(), 998),
+            Err(DecodeError::ByteIndexOutOfRange {
+                index: 998,
+                len: 998
+            })
+        );
+        assert_eq!(engine.decode(b"QUJD\n").as_deref(), Ok(&b"ABC"[..]));
+        assert_eq!(engine.decode_byte_at(b"QUJD\n", 0), Ok(b'A'));
+        assert_eq!(engine.decode_byte_at(b"QUI=\r\n", 1), Ok(b'B'));
+        assert_eq!(
+            engine.decode_byte_at(b"QUJD\n", 3),
+            Err(DecodeError::ByteIndexOutOfRange { index: 3, len: 3 })
+        );
+        assert_eq!(
+            engine.decode_byte_at(b"QUJD\n\n", 0),
+            Err(DecodeError::InvalidLength)
+        );
+        let unpadded = Base64::url_safe_no_pad();
+        assert_eq!(unpadded.decode_byte_at(b"QUI\n", 1), Ok(b'B'));
+        assert_eq!(
+            unpadded.decode_byte_at(b"QUI\n\n", 1),
+            Err(DecodeError::InvalidByte {
+                index: 3,
+                byte: b'\n'
+            })
+        );
+    }
+
+    #[test]
+    fn decode_windows_works() {
+        let engine = Base64::standard();
+        let windows = engine.decode_windows(b"bGlna*Qgdw==").collect::<Vec<_>>();
+        assert_eq!(
+            windows,
+            [
+                Ok((*b"lig", 3)),
+                Err(DecodeError::InvalidByte {
+                    index: 5,
+                    byte: b'*'
+                }),
+                Ok(([b'w', 0, 0], 1)),
+            ]
+        );
+
+        let engine = Base64::url_safe_no_pad();
+        let windows = engine.decode_windows(b"bGlnaHQgdw").collect::<Vec<_>>();
+        assert_eq!(windows.last(), Some(&Ok(([b'w', 0, 0], 1))));
+    }
+
+    #[test]
+    fn decode_with_scratch_works() {
+        let engine = Base64::standard();
+        let mut scratch = [0u8; 16];
+
+        let decoded = engine
+            .decode_with_scratch("bGlnaHQgd28=", &mut scratch)
+            .expect("should fit in scratch");
+        assert_eq!(decoded, b"light wo");
+        assert_eq!(decoded.iter().filter(|byte| **byte == b' ').count(), 1);
+
+        let err = engine
+            .decode_with_scratch("bGlnaHQgd29y", &mut scratch[..8])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::OutputTooSmall {
+                len: 9,
+                capacity: 8
+            }
+        );
+    }
+
+    #[test]
+    fn decode_tolerates_one_trailing_newline() {
+        let engine = Base64::standard();
+        assert_eq!(engine.decode("bGln\n").as_deref(), Ok(&b"lig"[..]));
+        assert_eq!(engine.decode("bGln\r\n").as_deref(), Ok(&b"lig"[..]));
+        let decoded = engine.decode_with_pad_count("bGlnaHQgdw==\n");
+        assert_eq!(decoded, Ok((b"light w".to_vec(), 2)));
+        assert_eq!(
+            engine.decode("bGlnaHQgdw==\n").as_deref(),
+            Ok(&b"light w"[..])
+        );
+        assert_eq!(
+            Base64::url_safe_no_pad().decode("bGlnaHQgdw\n").as_deref(),
+            Ok(&b"light w"[..])
+        );
+
+        assert!(engine.decode("bG\nln").is_err());
+        assert_eq!(
+            engine.decode("bG\nlaHQg"),
+            Err(DecodeError::InvalidByte {
+                index: 2,
+                byte: b'\n'
+            })
+        );
+        assert_eq!(engine.decode("bGln\n\n"), Err(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn decode_group_works() {
+        let engine = Base64::standard();
+        assert_eq!(engine.decode_group(*b"QUJD"), Ok((*b"ABC", 3)));
+        assert_eq!(engine.decode_group(*b"QUI="), Ok(([b'A', b'B', 0], 2)));
+        assert_eq!(engine.decode_group(*b"QQ=="), Ok(([b'A', 0, 0], 1)));
+
+        assert_eq!(
+            engine.decode_group(*b"Q=Q="),
+            Err(DecodeError::InvalidPadding { index: 1 })
+        );
+        assert_eq!(
+            engine.decode_group(*b"===="),
+            Err(DecodeError::InvalidPadding { index: 0 })
+        );
+    }
+
+    #[test]
+    fn decode_hints_at_wrong_alphabet() {
+        let err = Base64::url_safe().decode("a+b/").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::WrongAlphabet {
+                byte: b'+',
+                index: 1
+            }
+        );
+
+        let err = Base64::standard().decode("bGln_HQg").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::WrongAlphabet {
+                byte: b'_',
+                index: 4
+            }
+        );
+
+        let err = Base64::url_safe().decode("a*b/").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 1,
+                byte: b'*'
+            }
+        );
+    }
+
+    #[test]
+    fn decode_distinguishes_padding_from_zero_symbol() {
+        let engine = Base64::standard();
+        assert_eq!(engine.decode("QUJD").map(|d| d.len()), Ok(3));
+        assert_eq!(engine.decode("QUJ=").map(|d| d.len()), Ok(2));
+        assert_eq!(engine.decode("QU==").map(|d| d.len()), Ok(1));
+
+        assert_eq!(
+            engine.decode("QUAA").as_deref(),
+            Ok(&[0x41, 0x40, 0x00][..])
+        );
+        assert_eq!(engine.decode("QUA=").as_deref(), Ok(&[0x41, 0x40][..]));
+        assert_eq!(
+            engine.decode("QU=A"),
+            Err(DecodeError::InvalidPadding { index: 2 })
+        );
+    }
+
+    #[test]
+    fn decode_rejects_misplaced_padding() {
+        let engine = Base64::standard();
+
+        let err = engine.decode("=AAA").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 0 });
+
+        let err = engine.decode("QQ==QUJD").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 2 });
+
+        let err = engine.decode("QUJDQ=Q=").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 5 });
+
+        let err = engine.decode("====").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 0 });
+
+        let err = engine.decode("AA======").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 2 });
+
+        let err = engine.decode("QUJD====").unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 4 });
+
+        let decoded = engine.decode("AAAAQQ==").expect("should decode zeroes");
+        assert_eq!(decoded, [0, 0, 0, b'A']);
+    }
+
+    #[test]
+    fn decode_canonical_works() {
+        let engine = Base64::standard().require_canonical(true);
+
+        assert_eq!(engine.decode("QQ=="), Ok(b"A".to_vec()));
+        assert_eq!(engine.decode("QUI="), Ok(b"AB".to_vec()));
+        assert_eq!(engine.decode("QUJD"), Ok(b"ABC".to_vec()));
+
+        let err = engine.decode("QR==").unwrap_err();
+        assert_eq!(err, DecodeError::NonCanonical { index: 1 });
+        let err = engine.decode("QUJDQUJ=").unwrap_err();
+        assert_eq!(err, DecodeError::NonCanonical { index: 6 });
+
+        let lenient = Base64::standard();
+        assert_eq!(lenient.decode("QR=="), Ok(b"A".to_vec()));
+    }
+
+    #[test]
+    fn decode_with_budget_works() {
+        let engine = Base64::standard();
+        let encoded = engine.encode([1u8; 1023]);
+
+        assert!(Base64Engine::will_fit(encoded.len(), 1023));
+        assert!(!Base64Engine::will_fit(encoded.len(), 1022));
+
+        let err = engine.decode_with_budget(&encoded, 1022).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::ExceedsBudget {
+                len: 1023,
+                budget: 1022
+            }
+        );
+        assert_eq!(
+            engine.decode_with_budget(&encoded, 1023),
+            Ok(vec![1u8; 1023])
+        );
+        // unlike `will_fit`, padding is accounted for
+        assert_eq!(engine.decode_with_budget("QQ==", 1), Ok(vec![b'A']));
+    }
+
+    #[test]
+    fn decode_to_array_works() {
+        let engine = Base64::standard();
+        let input = (0..40u8).collect::<Vec<u8>>();
+        let encoded = engine.encode(&input);
+
+        let (decoded, len) = engine
+            .decode_to_array::<48>(&encoded)
+            .expect("should fit in the array");
+        assert_eq!(&decoded[..len], &input[..]);
+        assert!(decoded[len..].iter().all(|byte| *byte == 0));
+
+        let err = engine.decode_to_array::<39>(&encoded).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::OutputTooSmall {
+                len: 40,
+                capacity: 39
+            }
+        );
+    }
+
+    #[test]
+    fn is_valid_len_works() {
+        let engine = Base64::standard();
+        let key = engine.encode([0xA5; 32]);
+
+        assert!(engine.is_valid_len(&key, 32));
+        assert!(!engine.is_valid_len(&key, 31));
+        assert!(!engine.is_valid_len(key.replace('p', "*"), 32));
+    }
+
+    #[test]
+    fn decode_chars_works() {
+        let engine = Base64::standard();
+
+        assert_eq!(engine.decode_chars("QUJD".chars()), Ok(b"ABC".to_vec()));
+
+        let err = engine.decode_chars("QUé=".chars()).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 2,
+                byte: 0xC3
+            }
+        );
+    }
+
+    #[test]
+    fn decode_try_iter_works() {
+        let engine = Base64::standard();
+        let encoded = engine.encode(vec![0xa5; 1000]);
+
+        let decoded = engine.decode_try_iter(encoded.bytes().map(Ok::<u8, &str>));
+        assert_eq!(decoded, Ok(vec![0xa5; 1000]));
+
+        let failing = encoded.bytes().enumerate().map(|(i, byte)| {
+            if i == 600 {
+                Err("source failed")
+            } else {
+                Ok(byte)
+            }
+        });
+        let err = engine.decode_try_iter(failing).unwrap_err();
+        assert_eq!(err, DecodeTryError::Source("source failed"));
+
+        // same trailing line break tolerance as `decode`
+        for encoded in ["QQ==\n", "QUJD\r\n", "QUJDQQ==\n"] {
+            let decoded = engine.decode_try_iter(encoded.bytes().map(Ok::<u8, &str>));
+            assert_eq!(decoded.ok(), engine.decode(encoded).ok());
+        }
+        let decoded = engine.decode_try_iter(b"QQ==\n".iter().copied().map(Ok::<u8, &str>));
+        assert_eq!(decoded, Ok(vec![65]));
+
+        let err = engine
+            .decode_try_iter(b"bG*n".iter().copied().map(Ok::<u8, &str>))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DecodeTryError::Decode(DecodeError::InvalidByte {
+                index: 2,
+                byte: b'*'
+            })
+        );
+    }
+
+    #[test]
+    fn decode_skipping_works() {
+        let engine = Base64::standard();
+        let decoded = engine.decode_skipping("bGln\naHQg\nd28=\n", b"\n");
+        assert_eq!(decoded.as_deref(), Ok(&b"light wo"[..]));
+
+        let err = engine.decode_skipping("bGln\naHQg d28", b"\n").unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::InvalidByte {
+                index: 9,
+                byte: b' '
+            }
+        );
+    }
+
+    #[test]
+    fn decoded_len_from_parts_works() {
+        let config = [
+            ("bGlnaHQgd29y", 0, 9),
+            ("bGlnaHQgd28=", 1, 8),
+            ("bGlnaHQgdw==", 2, 7),
+            ("bGlnaHQgd28", 0, 8),
+            ("bGlnaHQgdw", 0, 7),
+            ("", 0, 0),
+        ];
+
+        for (encoded, pad_count, len) in config {
+            assert_eq!(
+                Base64Engine::decoded_len_from_parts(encoded.len(), pad_count),
+                len
+            );
+        }
+        const LEN: usize = Base64Engine::decoded_len_from_parts(16, 2);
+        assert_eq!(LEN, 10);
+    }
+
+    #[test]
+    fn decode_mapped_works() {
+        let engine = Base64::standard();
+        assert_eq!(
+            engine.decode_mapped("QUJD"),
+            Ok(vec![(b'A', 0), (b'B', 1), (b'C', 2)])
+        );
+        assert_eq!(
+            engine.decode_mapped("QUJDQUI="),
+            Ok(vec![(b'A', 0), (b'B', 1), (b'C', 2), (b'A', 4), (b'B', 5)])
+        );
+        assert_eq!(engine.decode_mapped("QUJ"), Err(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn decode_report_works() {
+        let engine = Base64::standard();
+        let (decoded, skipped) = engine.decode_report("bG*ln aHQgdw==");
+        assert_eq!(decoded, b"light w");
+        assert_eq!(skipped, [(2, b'*'), (5, b' ')]);
+
+        let (decoded, skipped) = engine.decode_report("bGl=naHQgd\n");
+        assert_eq!(decoded, b"light ");
+        assert_eq!(skipped, [(3, b'='), (9, b'd'), (10, b'\n')]);
+    }
+
+    #[test]
+    fn decode_with_pad_count_works() {
+        let engine = Base64::standard();
+        let config = [
+            ("bGlnaHQgdw==", "light w", 2),
+            ("bGlnaHQgd28=", "light wo", 1),
+            ("bGlnaHQgd29y", "light wor", 0),
+        ];
+
+        for (input, output, padding) in config {
+            let decoded = engine.decode_with_pad_count(input);
+            assert_eq!(decoded, Ok((output.as_bytes().to_vec(), padding)));
+        }
+
+        let decoded = Base64::url_safe_no_pad().decode_with_pad_count("bGlnaHQgdw");
+        assert_eq!(decoded, Ok((b"light w".to_vec(), 0)));
+    }
+
+    #[test]
+    fn decode_boxed_works() {
+        let engine = Base64::standard();
+
+        for encoded in ["bGlnaHQgdw==", "bGlnaHQgd28=", "bGlnaHQgd29y"] {
+            let decoded = engine.decode(encoded).expect("should decode");
+            assert_eq!(decoded.capacity(), decoded.len());
+
+            let boxed = engine.decode_boxed(encoded).expect("should decode");
+            assert_eq!(boxed, decoded.into_boxed_slice());
+        }
+    }
+
+    #[test]
+    fn decode_arc_works() {
+        let engine = Base64::standard();
+        let encoded = engine.encode("Many hands make light work");
+
+        let decoded = engine.decode_arc(&encoded).expect("should decode");
+        let expected = engine.decode(&encoded).expect("should decode");
+        assert_eq!(&decoded[..], &expected[..]);
+    }
+
+    #[test]
+    fn decode_const_works() {
+        const DECODED: [u8; 3] = Base64::standard().decode_const(b"QUJD");
+        const DECODED_PADDED: [u8; 2] = Base64::url_safe().decode_const(b"-_8=");
+        const DECODED_NO_PAD: [u8; 1] = Base64::url_safe_no_pad().decode_const(b"_w");
+
+        assert_eq!(&DECODED, b"ABC");
+        assert_eq!(DECODED_PADDED, [0xfb, 0xff]);
+        assert_eq!(DECODED_NO_PAD, [0xff]);
     }
 }
@@ -0,0 +1,41 @@
+/// Encodes a byte string at compile time into a `&'static str`, with the standard engine or
+/// the given one, e.g. to embed tokens without any runtime cost.
+#[macro_export]
+macro_rules! const_encode {
+    ($input:expr) => {
+        $crate::const_encode!($crate::Base64::standard(), $input)
+    };
+    ($engine:expr, $input:expr) => {{
+        const ENGINE: $crate::Base64Engine = $engine;
+        const INPUT: &[u8] = $input;
+        const LEN: usize = ENGINE.encoded_len(INPUT.len());
+        const ENCODED: [u8; LEN] = ENGINE.encode_const::<LEN>(INPUT);
+        const ENCODED_STR: &str = match ::core::str::from_utf8(&ENCODED) {
+            Ok(encoded) => encoded,
+            Err(_) => panic!("encoded output is always ASCII"),
+        };
+        ENCODED_STR
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Base64;
+
+    #[test]
+    fn const_encode_matches_encode() {
+        assert_eq!(const_encode!(b""), Base64::standard().encode(b""));
+        assert_eq!(
+            const_encode!(b"secret"),
+            Base64::standard().encode(b"secret")
+        );
+        assert_eq!(
+            const_encode!(b"light w"),
+            Base64::standard().encode(b"light w")
+        );
+        assert_eq!(
+            const_encode!(Base64::url_safe_no_pad(), b"\xfb\xff\xbf\xfe"),
+            Base64::url_safe_no_pad().encode(b"\xfb\xff\xbf\xfe")
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::{Base64Engine, DecodeError};
+
+impl Base64Engine {
+    // multiple of 4, so that every chunk starts at the beginning of a window
+    const PAR_CHUNK_LEN: usize = 64 * 1024;
+
+    /// Decodes `encoded` on the rayon thread pool, splitting it in chunks of whole windows
+    /// decoded in parallel, then concatenated in order.
+    ///
+    /// Same output and errors as `decode`, the error reported being the first one of the input.
+    pub fn par_decode(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let bytes = Self::trim_newline(encoded.as_ref());
+        let result = self.par_decode_trimmed(bytes);
+        if result.is_err() && Self::hints_base32(bytes) {
+            return Err(DecodeError::LikelyBase32);
+        }
+
+        result
+    }
+
+    // same as `par_decode`, for input whose trailing line break was already trimmed
+    fn par_decode_trimmed(&self, bytes: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // unpadded input is only accepted by engines that do not emit padding
+        if self.padding.is_some() && !bytes.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let chunks = bytes
+            .par_chunks(Self::PAR_CHUNK_LEN)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let start = i * Self::PAR_CHUNK_LEN;
+                let mut decoded = Vec::with_capacity(chunk.len() / 4 * 3);
+                for (offset, window) in (start..).step_by(4).zip(chunk.chunks(4)) {
+                    // padding may only show up at the end of the last window of the last chunk
+                    let is_last = offset + 4 >= bytes.len();
+                    let (window_bytes, len) = self.decode_window(window, offset, is_last)?;
+                    decoded.extend_from_slice(&window_bytes[..len]);
+                }
+
+                Ok(decoded)
+            })
+            .collect::<Vec<Result<Vec<u8>, DecodeError>>>();
+
+        let mut decoded = Vec::with_capacity(self.reserved_len(self.decoded_len(bytes)));
+        for chunk in chunks {
+            decoded.extend_from_slice(&chunk?);
+        }
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn par_decode_matches_decode() {
+        let input = (0..3 * 1024 * 1024 + 1)
+            .map(|i: u32| (i * 31 % 251) as u8)
+            .collect::<Vec<u8>>();
+
+        for engine in [Base64::standard(), Base64::url_safe_no_pad()] {
+            let encoded = engine.encode(&input);
+            assert!(encoded.len() >= 4 * 1024 * 1024);
+
+            let decoded = engine.par_decode(&encoded);
+            assert_eq!(decoded, engine.decode(&encoded));
+            assert_eq!(decoded.as_deref(), Ok(&input[..]));
+        }
+    }
+
+    #[test]
+    fn par_decode_reports_first_error() {
+        let engine = Base64::standard();
+        let mut encoded = engine.encode(vec![0u8; 3 * 64 * 1024]).into_bytes();
+        encoded[200_000] = b'*';
+        encoded[100_000] = b'=';
+
+        let err = engine.par_decode(&encoded).unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 100_000 });
+        assert_eq!(engine.decode(&encoded), Err(err));
+
+        for encoded in ["MZXW6YTBOI======", "MZXW6YTBOI======\n"] {
+            assert_eq!(engine.par_decode(encoded), Err(DecodeError::LikelyBase32));
+            assert_eq!(engine.decode(encoded), Err(DecodeError::LikelyBase32));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, DecodeError};
+
+impl Base64Engine {
+    const PEM_BEGIN: &'static str = "-----BEGIN ";
+    const PEM_END: &'static str = "-----END ";
+    const PEM_DASHES: &'static str = "-----";
+
+    /// Decodes the body of a PEM block, e.g. a certificate or a key.
+    ///
+    /// The `-----BEGIN <label>-----` and `-----END <label>-----` lines are stripped, as well as
+    /// any whitespace in between, and both labels must match.
+    pub fn decode_pem(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        let begin = lines
+            .next()
+            .and_then(|line| Self::pem_label(line, Self::PEM_BEGIN))
+            .ok_or(DecodeError::InvalidPem)?;
+        let end = lines
+            .next_back()
+            .and_then(|line| Self::pem_label(line, Self::PEM_END))
+            .ok_or(DecodeError::InvalidPem)?;
+
+        if begin != end {
+            return Err(DecodeError::PemLabelMismatch {
+                begin: begin.to_string(),
+                end: end.to_string(),
+            });
+        }
+
+        let body = lines
+            .flat_map(str::bytes)
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect::<Vec<u8>>();
+
+        self.decode(body)
+    }
+
+    fn pem_label<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+        line.strip_prefix(prefix)?.strip_suffix(Self::PEM_DASHES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{Base64, DecodeError};
+
+    #[test]
+    fn pem_decode_works() {
+        let pem = "
+            -----BEGIN TEST MESSAGE-----
+            TWFueSBoYW5kcyBtYWtl
+            IGxpZ2h0IHdvcms=
+            -----END TEST MESSAGE-----
+        ";
+
+        let decoded = Base64::standard()
+            .decode_pem(pem)
+            .expect("should decode pem body");
+        assert_eq!(decoded, b"Many hands make light work");
+    }
+
+    #[test]
+    fn pem_decode_rejects_mismatched_labels() {
+        let pem = "-----BEGIN PUBLIC KEY-----\nbGln\n-----END PRIVATE KEY-----\n";
+
+        let err = Base64::standard().decode_pem(pem).unwrap_err();
+        let expected = DecodeError::PemLabelMismatch {
+            begin: String::from("PUBLIC KEY"),
+            end: String::from("PRIVATE KEY"),
+        };
+        assert_eq!(err, expected);
+        assert_eq!(
+            Base64::standard().decode_pem("bGln"),
+            Err(DecodeError::InvalidPem)
+        );
+    }
+}
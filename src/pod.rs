@@ -0,0 +1,52 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+use bytemuck::Pod;
+
+use crate::{Base64Engine, DecodeError};
+
+impl Base64Engine {
+    /// Encodes the in-memory bytes of `data`, e.g. a `&[f32]`, in native byte order.
+    pub fn encode_pod<T: Pod>(&self, data: &[T]) -> String {
+        self.encode(bytemuck::cast_slice::<T, u8>(data))
+    }
+
+    /// Decodes `encoded` into elements of `T`, the reverse of [`Base64Engine::encode_pod`].
+    /// Errors if the decoded bytes are not a whole number of elements.
+    ///
+    /// The decoded bytes are copied into the returned `Vec`, so they need not be aligned for `T`.
+    pub fn decode_pod<T: Pod>(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<T>, DecodeError> {
+        let decoded = self.decode(encoded)?;
+        let size = mem::size_of::<T>();
+        if size == 0 || !decoded.len().is_multiple_of(size) {
+            return Err(DecodeError::UnalignedLength {
+                len: decoded.len(),
+                size,
+            });
+        }
+
+        Ok(bytemuck::pod_collect_to_vec(&decoded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn pod_works() {
+        let engine = Base64::standard();
+        let data = [1.5f32, -0.25, f32::MAX, 0.0];
+
+        let encoded = engine.encode_pod(&data);
+        assert_eq!(encoded.len(), engine.encoded_len(16));
+        assert_eq!(engine.decode_pod::<f32>(&encoded), Ok(data.to_vec()));
+
+        assert_eq!(
+            engine.decode_pod::<f32>("bGlnaHQ="),
+            Err(DecodeError::UnalignedLength { len: 5, size: 4 })
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::io::{self, BufRead, Read};
+
+use alloc::vec::Vec;
+
+use crate::transcode::Transcoder;
+use crate::Base64Engine;
+
+const BUF_LEN: usize = 1024;
+
+/// Reader that transcodes base64 read from `inner` with the `source` engine into base64 of the
+/// `target` engine, symbol by symbol and without decoding it.
+///
+/// Decoding errors are reported as [`io::ErrorKind::InvalidData`].
+#[derive(Debug)]
+pub struct TranscodeReader<R: Read> {
+    source: Base64Engine,
+    target: Base64Engine,
+    inner: R,
+    transcoder: Transcoder,
+    buf: [u8; BUF_LEN],
+    // padding required by `target`, emitted after `inner` reaches EOF
+    padding: Vec<u8>,
+    finished: bool,
+}
+
+impl<R: Read> TranscodeReader<R> {
+    pub fn new(source: Base64Engine, target: Base64Engine, inner: R) -> Self {
+        Self {
+            source,
+            target,
+            inner,
+            transcoder: Transcoder::default(),
+            buf: [0; BUF_LEN],
+            padding: Vec::new(),
+            finished: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let padding = self.transcoder.finish(&self.source, &self.target)?;
+
+        if let Some(pad) = self.target.padding {
+            self.padding.extend(core::iter::repeat_n(pad, padding));
+        }
+
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for TranscodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if self.finished {
+                let len = buf.len().min(self.padding.len());
+                buf[..len].copy_from_slice(&self.padding[..len]);
+                self.padding.drain(..len);
+                return Ok(len);
+            }
+
+            // symbols map one to one, so transcoded output never outgrows the input read
+            let len = buf.len().min(BUF_LEN);
+            let read = self.inner.read(&mut self.buf[..len])?;
+            if read == 0 {
+                self.finish()?;
+                continue;
+            }
+
+            let mut written = 0;
+            for byte in &self.buf[..read] {
+                let symbol = self.transcoder.map(&self.source, &self.target, *byte)?;
+
+                if let Some(symbol) = symbol {
+                    buf[written] = symbol;
+                    written += 1;
+                }
+            }
+
+            // a read made only of padding yields nothing, keep reading instead of signaling EOF
+            if written > 0 {
+                return Ok(written);
+            }
+        }
+    }
+}
+
+/// Reader of messages encoded with [`Base64Engine::encode_terminated`], decoding them one by
+/// one as an iterator.
+///
+/// A corrupted message is reported as [`io::ErrorKind::InvalidData`], and iteration resumes at
+/// the next terminator, so the messages after it are still decoded. So is trailing input that
+/// is not terminated, most likely a truncated message.
+#[derive(Debug)]
+pub struct TerminatedMessages<R: BufRead> {
+    engine: Base64Engine,
+    inner: R,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> TerminatedMessages<R> {
+    pub fn new(engine: Base64Engine, inner: R) -> Self {
+        Self {
+            engine,
+            inner,
+            line: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Iterator for TerminatedMessages<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line.clear();
+        match self
+            .inner
+            .read_until(Base64Engine::TERMINATOR, &mut self.line)
+        {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(err) => return Some(Err(err)),
+        }
+
+        let Some(message) = self.line.strip_suffix(&[Base64Engine::TERMINATOR]) else {
+            let err = io::Error::new(io::ErrorKind::InvalidData, "unterminated base64 message");
+            return Some(Err(err));
+        };
+
+        let decoded = self.engine.decode(message).map_err(io::Error::from);
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn transcode_reader_matches_transcode() {
+        let standard = Base64::standard();
+        let url_safe = Base64::url_safe();
+        let input = (0..8192u32)
+            .map(|i| (i * 7 % 256) as u8)
+            .collect::<Vec<u8>>();
+        let encoded = standard.encode(&input[..8191]);
+
+        let mut reader =
+            TranscodeReader::new(standard.clone(), url_safe.clone(), encoded.as_bytes());
+        let mut transcoded = String::new();
+        reader
+            .read_to_string(&mut transcoded)
+            .expect("should transcode stream");
+
+        let expected = standard
+            .transcode(&encoded, &url_safe)
+            .expect("should transcode");
+        assert_eq!(transcoded, expected);
+        assert_eq!(transcoded, url_safe.encode(&input[..8191]));
+    }
+
+    #[test]
+    fn terminated_messages_resyncs_after_corruption() {
+        let engine = Base64::standard();
+        let mut stream = engine.encode_terminated(b"light work").into_bytes();
+        stream.extend_from_slice(engine.encode_terminated(b"many hands").as_bytes());
+
+        let reader = TerminatedMessages::new(engine.clone(), stream.as_slice());
+        let messages = reader.collect::<io::Result<Vec<_>>>();
+        assert_eq!(
+            messages.ok(),
+            Some(vec![b"light work".to_vec(), b"many hands".to_vec()])
+        );
+
+        stream[2] = b'*';
+        stream.extend_from_slice(b"bGln");
+        let mut reader = TerminatedMessages::new(engine, stream.as_slice());
+        let err = reader.next().and_then(Result::err).map(|err| err.kind());
+        assert_eq!(err, Some(io::ErrorKind::InvalidData));
+        assert_eq!(
+            reader.next().and_then(Result::ok),
+            Some(b"many hands".to_vec())
+        );
+        let err = reader.next().and_then(Result::err).map(|err| err.kind());
+        assert_eq!(err, Some(io::ErrorKind::InvalidData));
+        assert!(reader.next().is_none());
+    }
+}
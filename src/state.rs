@@ -0,0 +1,399 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, DecodeError, EncodeError};
+
+/// Incremental encoder that carries up to 2 input bytes between calls to [`EncodeState::push`],
+/// so input can be fed in arbitrary pieces without `std::io`.
+#[derive(Clone, Debug)]
+pub struct EncodeState {
+    engine: Base64Engine,
+    buf: [u8; 3],
+    len: usize,
+}
+
+impl EncodeState {
+    pub fn new(engine: Base64Engine) -> Self {
+        Self {
+            engine,
+            buf: [0; 3],
+            len: 0,
+        }
+    }
+
+    /// Encodes every whole window of the buffered bytes followed by `input` into `out`,
+    /// buffering the 0 to 2 bytes left over.
+    pub fn push(&mut self, mut input: &[u8], out: &mut String) {
+        if self.len > 0 {
+            let len = input.len().min(3 - self.len);
+            self.buf[self.len..self.len + len].copy_from_slice(&input[..len]);
+            self.len += len;
+            input = &input[len..];
+
+            if self.len < 3 {
+                return;
+            }
+            self.engine.encode_into(&self.buf, out);
+            self.len = 0;
+        }
+
+        let remaining = input.len() % 3;
+        let (windows, rest) = input.split_at(input.len() - remaining);
+        self.engine.encode_into(windows, out);
+        self.buf[..remaining].copy_from_slice(rest);
+        self.len = remaining;
+    }
+
+    /// Same as `push`, but encodes into a fixed size `out`, only as much of `input` as fits,
+    /// e.g. for embedded streams. Returns how many bytes of `input` were consumed, how many
+    /// bytes of `out` were written, and whether nothing is left buffered, in which case the
+    /// message can end without calling `finalize_to_slice`.
+    ///
+    /// Windows are written whole, so `out` must have room for at least 4 bytes to make progress.
+    pub fn encode_bounded(&mut self, input: &[u8], out: &mut [u8]) -> (usize, usize, bool) {
+        let (mut consumed, mut written) = (0, 0);
+
+        if self.len > 0 {
+            let len = input.len().min(3 - self.len);
+            // a completed window must be written out right away, so it needs 4 bytes of room
+            if self.len + len == 3 && out.len() < 4 {
+                return (0, 0, false);
+            }
+
+            self.buf[self.len..self.len + len].copy_from_slice(&input[..len]);
+            self.len += len;
+            consumed = len;
+
+            if self.len < 3 {
+                return (consumed, 0, false);
+            }
+            written = self.encode_slice(&self.buf, out);
+            self.len = 0;
+        }
+
+        let windows = ((input.len() - consumed) / 3).min((out.len() - written) / 4);
+        let len = windows * 3;
+        written += self.encode_slice(&input[consumed..consumed + len], &mut out[written..]);
+        consumed += len;
+
+        // the 0 to 2 bytes left over once every window is written are carried to the next call
+        let rest = &input[consumed..];
+        if rest.len() < 3 {
+            self.buf[..rest.len()].copy_from_slice(rest);
+            self.len = rest.len();
+            consumed += rest.len();
+        }
+
+        (consumed, written, self.len == 0)
+    }
+
+    /// Same as `finalize`, but encodes into a fixed size `out`, returning the amount of bytes
+    /// written. Errors without clearing the state if `out` is too small.
+    pub fn finalize_to_slice(&mut self, out: &mut [u8]) -> Result<usize, EncodeError> {
+        let written = self.engine.encode_to_slice(&self.buf[..self.len], out)?;
+        self.len = 0;
+
+        Ok(written)
+    }
+
+    /// Returns the amount of input bytes, 0 to 2, buffered until a window is complete.
+    pub fn buffered_input(&self) -> usize {
+        self.len
+    }
+
+    /// Encodes the buffered bytes into `out`, padding them as configured by the engine, and
+    /// leaves the state ready to encode the next message.
+    pub fn finalize(&mut self, out: &mut String) {
+        self.engine.encode_into(&self.buf[..self.len], out);
+        self.len = 0;
+    }
+
+    /// Clears the state so it can encode the next message, discarding any buffered bytes that
+    /// were not finalized.
+    pub fn reset(&mut self) {
+        self.buf = [0; 3];
+        self.len = 0;
+    }
+
+    // encodes whole windows already known to fit in `out`
+    fn encode_slice(&self, windows: &[u8], out: &mut [u8]) -> usize {
+        self.engine
+            .encode_to_slice(windows, out)
+            .expect("should have room for every window")
+    }
+}
+
+/// Incremental decoder that carries up to 3 input chars between calls to [`DecodeState::push`],
+/// so input can be fed in arbitrary pieces without `std::io`.
+///
+/// Like `decode`, a single trailing `\n` or `\r\n` is accepted at the end of the whole input,
+/// wherever the pieces are split.
+#[derive(Clone, Debug)]
+pub struct DecodeState {
+    engine: Base64Engine,
+    buf: [u8; 4],
+    len: usize,
+    // amount of input chars decoded, used to report errors
+    offset: usize,
+    // index of the padding of the last window, after which no more input is allowed
+    padding: Option<usize>,
+    // trailing line break, held back until `finalize` as only the end of the input may have it
+    newline: [u8; 2],
+    newline_len: usize,
+}
+
+impl DecodeState {
+    pub fn new(engine: Base64Engine) -> Self {
+        Self {
+            engine,
+            buf: [0; 4],
+            len: 0,
+            offset: 0,
+            padding: None,
+            newline: [0; 2],
+            newline_len: 0,
+        }
+    }
+
+    /// Decodes every whole window of the buffered chars followed by `input` into `out`,
+    /// buffering the 0 to 3 chars left over.
+    pub fn push(&mut self, input: &[u8], out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        let end = match self.newline_len {
+            0 => input
+                .iter()
+                .position(|byte| matches!(byte, b'\r' | b'\n'))
+                .unwrap_or(input.len()),
+            _ => 0,
+        };
+        let (input, newline) = input.split_at(end);
+        self.push_windows(input, out)?;
+
+        for &byte in newline {
+            let valid = match self.newline_len {
+                0 => true,
+                1 => self.newline[0] == b'\r' && byte == b'\n',
+                _ => false,
+            };
+            if !valid {
+                let index = self.offset + self.len + self.newline_len;
+                return Err(DecodeError::InvalidByte { index, byte });
+            }
+            self.newline[self.newline_len] = byte;
+            self.newline_len += 1;
+        }
+
+        Ok(())
+    }
+
+    // decodes windows as `push`, for input without any line break
+    fn push_windows(&mut self, mut input: &[u8], out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        while !input.is_empty() {
+            if let Some(index) = self.padding {
+                return Err(DecodeError::InvalidPadding { index });
+            }
+
+            if self.len > 0 || input.len() < 4 {
+                let len = input.len().min(4 - self.len);
+                self.buf[self.len..self.len + len].copy_from_slice(&input[..len]);
+                self.len += len;
+                input = &input[len..];
+
+                if self.len == 4 {
+                    let buf = self.buf;
+                    self.decode_windows(&buf, out)?;
+                    self.len = 0;
+                }
+                continue;
+            }
+
+            let len = input.len() - input.len() % 4;
+            self.decode_windows(&input[..len], out)?;
+            input = &input[len..];
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the buffered chars into `out`, which are only valid for engines without padding.
+    pub fn finalize(self, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        if self.len > 0 {
+            self.engine
+                .decode_each_trimmed(&self.buf[..self.len], |window| {
+                    out.extend_from_slice(window)
+                })
+                .map_err(|err| err.with_offset(self.offset))?;
+        }
+
+        // a lone `\r` is not a line break
+        if self.newline[..self.newline_len] == *b"\r" {
+            let index = self.offset + self.len;
+            return Err(DecodeError::InvalidByte { index, byte: b'\r' });
+        }
+
+        Ok(())
+    }
+
+    fn decode_windows(&mut self, windows: &[u8], out: &mut Vec<u8>) -> Result<(), DecodeError> {
+        let len = out.len();
+        self.engine
+            .decode_each_trimmed(windows, |window| out.extend_from_slice(window))
+            .map_err(|err| err.with_offset(self.offset))?;
+
+        // only the last window may carry padding, decoding to less than 3 bytes
+        let last_window = windows.len() - 4;
+        let last_len = out.len() - len - last_window / 4 * 3;
+        if last_len < 3 {
+            self.padding = Some(self.offset + last_window + last_len + 1);
+        }
+
+        self.offset += windows.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn encode_bounded_works() {
+        let engine = Base64::standard();
+        let input = (0..100u32)
+            .map(|i| (i * 7 % 256) as u8)
+            .collect::<Vec<u8>>();
+
+        for (piece_len, out_len) in [(7, 5), (1, 4), (64, 9), (2, 16)] {
+            let mut state = EncodeState::new(engine.clone());
+            let mut out = [0u8; 16];
+            let mut encoded = Vec::new();
+
+            for mut piece in input.chunks(piece_len) {
+                while !piece.is_empty() {
+                    let (consumed, written, _) = state.encode_bounded(piece, &mut out[..out_len]);
+                    encoded.extend_from_slice(&out[..written]);
+                    piece = &piece[consumed..];
+                }
+            }
+
+            let (_, _, finished) = state.encode_bounded(&[], &mut out[..out_len]);
+            assert_eq!(finished, state.buffered_input() == 0);
+            let written = state
+                .finalize_to_slice(&mut out[..out_len])
+                .expect("should fit the last window");
+            encoded.extend_from_slice(&out[..written]);
+            assert_eq!(encoded, engine.encode(&input).as_bytes());
+        }
+    }
+
+    #[test]
+    fn encode_state_reset_works() {
+        let engine = Base64::standard();
+        let mut state = EncodeState::new(engine.clone());
+        let messages: [&[u8]; 3] = [b"light w", b"light wo", b"light wor"];
+
+        for message in messages {
+            // leave a partial window buffered that must not leak into the next message
+            let mut discarded = String::new();
+            state.push(b"xy", &mut discarded);
+            state.reset();
+
+            let mut encoded = String::new();
+            for piece in message.chunks(2) {
+                state.push(piece, &mut encoded);
+            }
+            state.finalize(&mut encoded);
+
+            assert!(discarded.is_empty());
+            assert_eq!(encoded, engine.encode(message));
+        }
+    }
+
+    #[test]
+    fn decode_state_works() {
+        let engines = [Base64::standard(), Base64::url_safe_no_pad()];
+        let input = b"Many hands make light work";
+
+        for engine in engines {
+            let encoded = engine.encode(input);
+            for split in 0..=encoded.len() {
+                let (head, tail) = encoded.as_bytes().split_at(split);
+                let mut state = DecodeState::new(engine.clone());
+                let mut decoded = Vec::new();
+
+                state.push(head, &mut decoded).expect("should decode head");
+                state.push(tail, &mut decoded).expect("should decode tail");
+                state.finalize(&mut decoded).expect("should finalize");
+                assert_eq!(decoded, input);
+            }
+        }
+    }
+
+    #[test]
+    fn decode_state_accepts_trailing_newline() {
+        let engines = [Base64::standard(), Base64::url_safe_no_pad()];
+
+        for engine in engines {
+            for newline in ["\n", "\r\n"] {
+                let encoded = engine.encode(b"light w") + newline;
+                for split in 0..=encoded.len() {
+                    let (head, tail) = encoded.as_bytes().split_at(split);
+                    let mut state = DecodeState::new(engine.clone());
+                    let mut decoded = Vec::new();
+
+                    state.push(head, &mut decoded).expect("should decode head");
+                    state.push(tail, &mut decoded).expect("should decode tail");
+                    state.finalize(&mut decoded).expect("should finalize");
+                    assert_eq!(Ok(decoded), engine.decode(&encoded));
+                }
+            }
+        }
+
+        let decode = |pieces: &[&[u8]]| {
+            let mut state = DecodeState::new(Base64::standard());
+            let mut decoded = Vec::new();
+            for piece in pieces {
+                state.push(piece, &mut decoded)?;
+            }
+            state.finalize(&mut decoded)
+        };
+        assert_eq!(
+            decode(&[b"QQ==\n", b"\n"]),
+            Err(DecodeError::InvalidByte {
+                index: 5,
+                byte: b'\n'
+            })
+        );
+        assert_eq!(
+            decode(&[b"QUJD\n", b"QUJD"]),
+            Err(DecodeError::InvalidByte {
+                index: 5,
+                byte: b'Q'
+            })
+        );
+        assert_eq!(
+            decode(&[b"QQ==\r"]),
+            Err(DecodeError::InvalidByte {
+                index: 4,
+                byte: b'\r'
+            })
+        );
+    }
+
+    #[test]
+    fn decode_state_rejects_data_after_padding() {
+        let mut state = DecodeState::new(Base64::standard());
+        let mut decoded = Vec::new();
+
+        state.push(b"QQ==", &mut decoded).expect("should decode");
+        let err = state.push(b"QQ==", &mut decoded).unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 2 });
+
+        let mut state = DecodeState::new(Base64::standard());
+        state.push(b"QUJDQ", &mut decoded).expect("should decode");
+        let err = state.push(b"UI=QUJD", &mut decoded).unwrap_err();
+        assert_eq!(err, DecodeError::InvalidPadding { index: 7 });
+    }
+}
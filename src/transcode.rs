@@ -0,0 +1,289 @@
+use alloc::string::String;
+
+use crate::{Base64, Base64Engine, DecodeError};
+
+impl Base64Engine {
+    /// Re-encodes `encoded` with the `target` engine without decoding it, mapping each symbol
+    /// of this engine's alphabet to the symbol with the same 6-bit value in `target`.
+    ///
+    /// Padding is dropped or appended as required by `target`. Input is validated as `decode`
+    /// would, a single trailing `\n` or `\r\n` included, which is dropped as well.
+    pub fn transcode(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        target: &Base64Engine,
+    ) -> Result<String, DecodeError> {
+        let encoded = Self::trim_newline(encoded.as_ref());
+        // checked up front like `decode` does, before any symbol
+        if self.padding.is_some() && !encoded.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let mut transcoder = Transcoder::default();
+        let mut transcoded = String::with_capacity(self.reserved_len(encoded.len() + 2));
+
+        for byte in encoded {
+            if let Some(symbol) = transcoder.map(self, target, *byte)? {
+                transcoded.push(char::from(symbol));
+            }
+        }
+
+        let padding = transcoder.finish(self, target)?;
+        if let Some(pad) = target.padding {
+            transcoded.extend(core::iter::repeat_n(char::from(pad), padding));
+        }
+
+        Ok(transcoded)
+    }
+
+    /// Re-encodes `encoded` with the url-safe alphabet and without padding, e.g. to migrate
+    /// standard base64 to the form used by JWTs. Same as `transcode` with
+    /// [`Base64::url_safe_no_pad`] as target.
+    pub fn to_url_safe_no_pad(&self, encoded: &str) -> Result<String, DecodeError> {
+        self.transcode(encoded, &Base64::url_safe_no_pad())
+    }
+}
+
+/// Symbol by symbol transcoding state shared by [`Base64Engine::transcode`] and the streaming
+/// [`TranscodeReader`](crate::TranscodeReader).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Transcoder {
+    // amount of bytes seen so far, used to report errors
+    index: usize,
+    // amount of alphabet symbols seen so far
+    symbols: usize,
+    // index and 6-bit value of the last symbol, to check its unused bits
+    last_symbol: (usize, u8),
+    // index of the first padding byte, which must be part of the last window
+    first_pad: Option<usize>,
+}
+
+impl Transcoder {
+    /// Maps `byte` from `source` into `target`, returning `None` for padding.
+    ///
+    /// Input is validated as `decode` would: padding may only end the last window, taking 1
+    /// or 2 bytes of it.
+    pub(crate) fn map(
+        &mut self,
+        source: &Base64Engine,
+        target: &Base64Engine,
+        byte: u8,
+    ) -> Result<Option<u8>, DecodeError> {
+        let index = self.index;
+        self.index += 1;
+
+        match source.decode_table[usize::from(byte)] {
+            Base64Engine::INVALID_SYMBOL if source.padding == Some(byte) => {
+                let first_pad = *self.first_pad.get_or_insert(index);
+                match self.symbols % 4 {
+                    // a window made only of padding carries no data at all
+                    0 => Err(DecodeError::InvalidPadding { index }),
+                    // a single symbol carries less than a byte, whatever follows it
+                    1 => Err(DecodeError::InvalidLength),
+                    // padding past the end of its window
+                    _ if index >= first_pad - first_pad % 4 + 4 => {
+                        Err(DecodeError::InvalidPadding { index: first_pad })
+                    }
+                    _ => Ok(None),
+                }
+            }
+            Base64Engine::INVALID_SYMBOL if Base64Engine::is_builtin_symbol(byte) => {
+                Err(DecodeError::WrongAlphabet { byte, index })
+            }
+            Base64Engine::INVALID_SYMBOL => Err(DecodeError::InvalidByte { index, byte }),
+            idx => {
+                // a symbol following padding makes the padding itself misplaced
+                if let Some(first_pad) = self.first_pad {
+                    return Err(DecodeError::InvalidPadding { index: first_pad });
+                }
+
+                self.symbols += 1;
+                self.last_symbol = (index, idx);
+                Ok(Some(target.alphabet[usize::from(idx)]))
+            }
+        }
+    }
+
+    /// Validates the consumed input length, as well as the unused bits of the last symbol if
+    /// `source` requires canonical encodings, and returns how many padding chars `target`
+    /// needs.
+    pub(crate) fn finish(
+        &self,
+        source: &Base64Engine,
+        target: &Base64Engine,
+    ) -> Result<usize, DecodeError> {
+        let remaining = self.symbols % 4;
+        let padded_len = source.padding.is_some() && !self.index.is_multiple_of(4);
+        if remaining == 1 || padded_len {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        // the last of 2 symbols carries 4 unused bits, the last of 3 carries 2
+        let (index, value) = self.last_symbol;
+        let unused_bits = match remaining {
+            2 => value & 0x0F,
+            3 => value & 0x03,
+            _ => 0,
+        };
+        if source.require_canonical && unused_bits != 0 {
+            return Err(DecodeError::NonCanonical { index });
+        }
+
+        match target.padding {
+            Some(_) if remaining != 0 => Ok(4 - remaining),
+            _ => Ok(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Base64, DecodeError};
+
+    #[test]
+    fn transcode_works() {
+        let standard = Base64::standard();
+        let url_safe_no_pad = Base64::url_safe_no_pad();
+        let input = [0xfb, 0xff, 0xbf, 0xfe];
+
+        let encoded = standard.encode(input);
+        assert_eq!(encoded, "+/+//g==");
+
+        let transcoded = standard
+            .transcode(&encoded, &url_safe_no_pad)
+            .expect("should transcode");
+        assert_eq!(transcoded, url_safe_no_pad.encode(input));
+
+        let back = url_safe_no_pad
+            .transcode(&transcoded, &standard)
+            .expect("should transcode back");
+        assert_eq!(back, encoded);
+    }
+
+    #[test]
+    fn transcode_rejects_invalid_input() {
+        let standard = Base64::standard();
+        let url_safe = Base64::url_safe();
+
+        let err = standard.transcode("bG-n", &url_safe).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::WrongAlphabet {
+                byte: b'-',
+                index: 2
+            }
+        );
+
+        let err = standard.transcode("bGl", &url_safe).unwrap_err();
+        assert_eq!(err, DecodeError::InvalidLength);
+    }
+
+    #[test]
+    fn transcode_rejects_what_decode_rejects() {
+        let standard = Base64::standard();
+        let url_safe = Base64::url_safe();
+        let config = [
+            ("====", DecodeError::InvalidPadding { index: 0 }),
+            ("QUJD====", DecodeError::InvalidPadding { index: 4 }),
+            ("QQ======", DecodeError::InvalidPadding { index: 2 }),
+            ("QQ=A", DecodeError::InvalidPadding { index: 2 }),
+            ("QQ==QUI=", DecodeError::InvalidPadding { index: 2 }),
+            ("Q===", DecodeError::InvalidLength),
+            (
+                "QU-D",
+                DecodeError::WrongAlphabet {
+                    byte: b'-',
+                    index: 2,
+                },
+            ),
+            (
+                "QU*D",
+                DecodeError::InvalidByte {
+                    index: 2,
+                    byte: b'*',
+                },
+            ),
+            ("QUJD\n\n", DecodeError::InvalidLength),
+            ("QUJD\r", DecodeError::InvalidLength),
+        ];
+
+        for (input, err) in config {
+            assert_eq!(
+                standard.decode(input),
+                Err(err.clone()),
+                "decoding {input:?}"
+            );
+            assert_eq!(
+                standard.transcode(input, &url_safe),
+                Err(err),
+                "transcoding {input:?}"
+            );
+        }
+
+        // a single trailing line break is dropped, as `decode` tolerates it
+        for input in ["QUJD\n", "QUI=\r\n"] {
+            let transcoded = standard
+                .transcode(input, &url_safe)
+                .expect("should transcode");
+            assert_eq!(url_safe.decode(&transcoded), standard.decode(input));
+            assert!(!transcoded.ends_with('\n'));
+        }
+    }
+
+    #[test]
+    fn transcode_checks_canonical_encodings() {
+        let imap = Base64::imap();
+        let err = DecodeError::NonCanonical { index: 2 };
+        assert_eq!(imap.decode("AOl"), Err(err.clone()));
+        assert_eq!(imap.transcode("AOl", &Base64::standard()), Err(err));
+        assert_eq!(
+            imap.transcode("AOk", &Base64::standard()).as_deref(),
+            Ok("AOk=")
+        );
+
+        let strict = Base64::standard().require_canonical(true);
+        assert_eq!(
+            strict.transcode("QR==", &Base64::url_safe()),
+            Err(DecodeError::NonCanonical { index: 1 })
+        );
+        assert_eq!(
+            Base64::standard()
+                .transcode("QR==", &Base64::url_safe())
+                .as_deref(),
+            Ok("QR==")
+        );
+    }
+
+    #[test]
+    fn to_url_safe_no_pad_works() {
+        let standard = Base64::standard();
+        let migrated = standard.to_url_safe_no_pad("+/+//g==");
+        assert_eq!(migrated.as_deref(), Ok("-_-__g"));
+
+        let back = Base64::url_safe_no_pad().transcode("-_-__g", &standard);
+        assert_eq!(back.as_deref(), Ok("+/+//g=="));
+
+        assert_eq!(
+            standard.to_url_safe_no_pad("+/+//g="),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            standard.to_url_safe_no_pad("===="),
+            Err(DecodeError::InvalidPadding { index: 0 })
+        );
+        assert_eq!(
+            standard.to_url_safe_no_pad("QUJD===="),
+            Err(DecodeError::InvalidPadding { index: 4 })
+        );
+        assert_eq!(
+            standard.to_url_safe_no_pad("QQ======"),
+            Err(DecodeError::InvalidPadding { index: 2 })
+        );
+        assert_eq!(
+            standard.to_url_safe_no_pad("+/+_"),
+            Err(DecodeError::WrongAlphabet {
+                byte: b'_',
+                index: 3
+            })
+        );
+    }
+}
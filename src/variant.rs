@@ -0,0 +1,193 @@
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Base64, Base64Engine, UnknownVariant};
+
+/// One of the built-in engine presets of [`Base64`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    Standard,
+    UrlSafe,
+    UrlSafeNoPad,
+    Imap,
+}
+
+impl Variant {
+    /// Returns the preset engine of the variant.
+    pub const fn engine(self) -> Base64Engine {
+        match self {
+            Self::Standard => Base64::standard(),
+            Self::UrlSafe => Base64::url_safe(),
+            Self::UrlSafeNoPad => Base64::url_safe_no_pad(),
+            Self::Imap => Base64::imap(),
+        }
+    }
+
+    /// Returns the canonical kebab-case name of the variant, e.g. `url-safe-no-pad`, which
+    /// [`Variant::from_str`] parses back.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::UrlSafe => "url-safe",
+            Self::UrlSafeNoPad => "url-safe-no-pad",
+            Self::Imap => "imap",
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Variant {
+    type Err = UnknownVariant;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Base64::variants()
+            .iter()
+            .copied()
+            .find(|variant| variant.name() == name)
+            .ok_or_else(|| UnknownVariant {
+                name: String::from(name),
+            })
+    }
+}
+
+impl Base64 {
+    /// Returns every built-in variant, e.g. to list them in a CLI.
+    pub const fn variants() -> &'static [Variant] {
+        &[
+            Variant::Standard,
+            Variant::UrlSafe,
+            Variant::UrlSafeNoPad,
+            Variant::Imap,
+        ]
+    }
+
+    /// Guesses which built-in variant `input` was most likely encoded with, from the symbols
+    /// specific to each alphabet and the presence of padding.
+    ///
+    /// Returns `None` for ambiguous input, i.e. made only of symbols shared by every alphabet,
+    /// as well as for input mixing both alphabets or containing any other byte.
+    pub fn sniff(input: &str) -> Option<Variant> {
+        let (mut standard, mut url_safe, mut imap) = (false, false, false);
+        for byte in input.trim_end_matches('=').bytes() {
+            match byte {
+                // `+` is shared by the standard and IMAP alphabets
+                b'+' | b'/' => standard = true,
+                b'-' | b'_' => url_safe = true,
+                b',' => imap = true,
+                byte if byte.is_ascii_alphanumeric() => {}
+                _ => return None,
+            }
+        }
+
+        let padded = input.ends_with('=');
+        match (standard, url_safe, imap) {
+            (true, false, false) => Some(Variant::Standard),
+            (false, true, false) if padded => Some(Variant::UrlSafe),
+            (false, true, false) => Some(Variant::UrlSafeNoPad),
+            (_, false, true) if !padded && !input.contains('/') => Some(Variant::Imap),
+            _ => None,
+        }
+    }
+
+    /// Returns whether `input` looks like padded base32 of RFC 4648 rather than base64, i.e. it
+    /// is made of `A-Z2-7` symbols followed by a valid amount of `=`, 8 chars per window.
+    ///
+    /// This is a heuristic, most such input being valid base64 as well.
+    pub fn looks_like_base32(input: &[u8]) -> bool {
+        let symbols = input.len() - input.iter().rev().take_while(|byte| **byte == b'=').count();
+
+        !input.is_empty()
+            && input.len().is_multiple_of(8)
+            && matches!(input.len() - symbols, 0 | 1 | 3 | 4 | 6)
+            && input[..symbols]
+                .iter()
+                .all(|byte| matches!(byte, b'A'..=b'Z' | b'2'..=b'7'))
+    }
+
+    /// Returns which byte values show up in `input`, indexed by byte, e.g. to tell which
+    /// alphabet an unknown token uses before picking an engine to decode it.
+    pub fn symbols_used(input: impl AsRef<[u8]>) -> [bool; 256] {
+        let mut used = [false; 256];
+        for byte in input.as_ref() {
+            used[usize::from(*byte)] = true;
+        }
+
+        used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn variant_from_str_works() {
+        let variant = "url-safe".parse::<Variant>();
+        assert_eq!(variant, Ok(Variant::UrlSafe));
+        assert_eq!(variant.map(Variant::engine), Ok(Base64::url_safe()));
+
+        for variant in Base64::variants() {
+            assert_eq!(variant.to_string().parse(), Ok(*variant));
+        }
+
+        let err = "url_safe".parse::<Variant>().unwrap_err();
+        assert_eq!(err.name, "url_safe");
+    }
+
+    #[test]
+    fn sniff_detects_url_safe() {
+        assert_eq!(Base64::sniff("-_8="), Some(Variant::UrlSafe));
+        assert_eq!(Base64::sniff("-_8"), Some(Variant::UrlSafeNoPad));
+
+        let engine = Base64::sniff("-_8")
+            .map(Variant::engine)
+            .expect("should sniff");
+        assert_eq!(engine.decode("-_8").as_deref(), Ok(&[0xfb, 0xff][..]));
+    }
+
+    #[test]
+    fn sniff_detects_standard() {
+        assert_eq!(Base64::sniff("+/8="), Some(Variant::Standard));
+        assert_eq!(Base64::sniff("+/+//g=="), Some(Variant::Standard));
+        assert_eq!(Base64::sniff("+,+,,g"), Some(Variant::Imap));
+        assert_eq!(Base64::sniff("+/+,,g"), None);
+    }
+
+    #[test]
+    fn sniff_rejects_ambiguous_input() {
+        assert_eq!(Base64::sniff("bGlnaHQgdw=="), None);
+        assert_eq!(Base64::sniff("bGlnaHQgdw"), None);
+        assert_eq!(Base64::sniff("+/-_"), None);
+        assert_eq!(Base64::sniff("bG n"), None);
+    }
+
+    #[test]
+    fn looks_like_base32_works() {
+        assert!(Base64::looks_like_base32(b"MZXW6YTBOI======"));
+        assert!(Base64::looks_like_base32(b"MZXW6YQ="));
+        assert!(Base64::looks_like_base32(b"MZXW6YTB"));
+        assert!(!Base64::looks_like_base32(b""));
+        assert!(!Base64::looks_like_base32(b"MZXW6YTBOI====="));
+        assert!(!Base64::looks_like_base32(b"MZXW6Y=="));
+        assert!(!Base64::looks_like_base32(b"bGlnaHQgdw=="));
+        assert!(!Base64::looks_like_base32(b"QQ==QUI="));
+    }
+
+    #[test]
+    fn symbols_used_works() {
+        let token = Base64::url_safe_no_pad().encode([0xfb, 0xff, 0xbf, 0xfe, b'l']);
+        let used = Base64::symbols_used(token);
+        assert!(used[usize::from(b'-')] && used[usize::from(b'_')]);
+        assert!(!used[usize::from(b'+')] && !used[usize::from(b'/')]);
+        assert!(!used[usize::from(b'=')]);
+        assert_eq!(used.iter().filter(|used| **used).count(), 4);
+    }
+}
@@ -0,0 +1,175 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, DecodeError};
+
+impl Base64Engine {
+    /// Same as `decode`, but also checks that the decoded output is `expected_len` bytes long
+    /// and that its CRC-32 (IEEE) is `expected_crc32`, e.g. for downloads with a known checksum.
+    pub fn decode_verify(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        expected_len: usize,
+        expected_crc32: u32,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let decoded = self.decode(encoded)?;
+
+        let crc32 = crc32(&decoded);
+        if decoded.len() != expected_len || crc32 != expected_crc32 {
+            return Err(DecodeError::IntegrityMismatch {
+                len: decoded.len(),
+                crc32,
+            });
+        }
+
+        Ok(decoded)
+    }
+
+    /// Decodes `encoded` and compares the output with `expected` in constant time, e.g. to
+    /// check a MAC or token without leaking through timing how much of it matched.
+    ///
+    /// Only the comparison is constant-time: decoding itself, and outputs of a different length
+    /// than `expected`, which are unequal right away, take time depending on the input.
+    pub fn decode_eq_ct(
+        &self,
+        encoded: impl AsRef<[u8]>,
+        expected: &[u8],
+    ) -> Result<bool, DecodeError> {
+        let decoded = self.decode(encoded)?;
+        if decoded.len() != expected.len() {
+            return Ok(false);
+        }
+
+        // folds every byte in, never stopping at the first difference
+        let diff = decoded
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| core::hint::black_box(diff | (a ^ b)));
+        Ok(diff == 0)
+    }
+
+    /// Encodes `input` followed by a check symbol, the sum of the 6-bit values of every symbol
+    /// modulo 64, so that [`Base64Engine::decode_checked`] catches any single mistyped symbol
+    /// of e.g. manually entered keys.
+    pub fn encode_checked(&self, input: impl AsRef<[u8]>) -> String {
+        let mut encoded = self.encode(input);
+        let check = self.checksum(encoded.as_bytes());
+        encoded.push(char::from(self.alphabet[usize::from(check)]));
+
+        encoded
+    }
+
+    /// Decodes the output of [`Base64Engine::encode_checked`], verifying its check symbol.
+    pub fn decode_checked(&self, encoded: impl AsRef<[u8]>) -> Result<Vec<u8>, DecodeError> {
+        let (check, body) = encoded
+            .as_ref()
+            .split_last()
+            .ok_or(DecodeError::InvalidLength)?;
+        let decoded = self.decode(body)?;
+
+        let index = body.len();
+        match self.decode_table[usize::from(*check)] {
+            Self::INVALID_SYMBOL => Err(DecodeError::InvalidByte {
+                index,
+                byte: *check,
+            }),
+            check if check != self.checksum(body) => Err(DecodeError::InvalidChecksum { index }),
+            _ => Ok(decoded),
+        }
+    }
+
+    // sum of the 6-bit values of the symbols of valid `encoded`, padding excluded, modulo 64
+    fn checksum(&self, encoded: &[u8]) -> u8 {
+        let sum = encoded
+            .iter()
+            .map(|byte| self.decode_table[usize::from(*byte)])
+            .filter(|value| *value != Self::INVALID_SYMBOL)
+            .fold(0u8, u8::wrapping_add);
+
+        sum % 64
+    }
+}
+
+// bitwise CRC-32 with the reflected IEEE polynomial, as used by zip, gzip and png
+fn crc32(bytes: &[u8]) -> u32 {
+    const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (POLYNOMIAL & mask);
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn decode_verify_works() {
+        let engine = Base64::standard();
+        let encoded = engine.encode(b"123456789");
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let decoded = engine.decode_verify(&encoded, 9, 0xCBF4_3926);
+        assert_eq!(decoded.as_deref(), Ok(&b"123456789"[..]));
+
+        let expected = DecodeError::IntegrityMismatch {
+            len: 9,
+            crc32: 0xCBF4_3926,
+        };
+        assert_eq!(
+            engine.decode_verify(&encoded, 9, 0xCBF4_3927),
+            Err(expected.clone())
+        );
+        assert_eq!(
+            engine.decode_verify(&encoded, 8, 0xCBF4_3926),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn decode_checked_detects_single_symbol_typos() {
+        let engine = Base64::standard();
+        let encoded = engine.encode_checked(b"light work");
+        assert_eq!(encoded, "bGlnaHQgd29yaw==K");
+        assert_eq!(
+            engine.decode_checked(&encoded).as_deref(),
+            Ok(&b"light work"[..])
+        );
+
+        let mut typo = encoded.into_bytes();
+        typo[3] = b'm';
+        let err = engine.decode_checked(&typo).unwrap_err();
+        assert_eq!(err, DecodeError::InvalidChecksum { index: 16 });
+        assert_eq!(engine.decode_checked(""), Err(DecodeError::InvalidLength));
+    }
+
+    #[test]
+    fn decode_eq_ct_works() {
+        let engine = Base64::url_safe_no_pad();
+        let token = [0xfb, 0xff, 0x00, 0x7f, 0x80];
+        let encoded = engine.encode(token);
+
+        assert_eq!(engine.decode_eq_ct(&encoded, &token), Ok(true));
+        assert_eq!(
+            engine.decode_eq_ct(&encoded, &[0xfb, 0xff, 0x00, 0x7f, 0x81]),
+            Ok(false)
+        );
+        assert_eq!(engine.decode_eq_ct(&encoded, &token[..4]), Ok(false));
+        assert_eq!(engine.decode_eq_ct(&encoded, &[]), Ok(false));
+        assert_eq!(
+            engine.decode_eq_ct("-_8A*4A", &token),
+            Err(DecodeError::InvalidByte {
+                index: 4,
+                byte: b'*'
+            })
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use alloc::string::String;
+
+use crate::{Base64Engine, EncodeError};
+
+/// Line break inserted between lines of wrapped output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+impl Base64Engine {
+    /// Returns the exact length of the encoding of `input_len` bytes wrapped every `line_len`
+    /// chars with `line_ending`. No line ending is emitted after the last line, and a `line_len`
+    /// of zero disables wrapping.
+    pub const fn encoded_len_wrapped(
+        &self,
+        input_len: usize,
+        line_len: usize,
+        line_ending: LineEnding,
+    ) -> usize {
+        let len = self.encoded_len(input_len);
+        if len == 0 || line_len == 0 {
+            return len;
+        }
+
+        let line_breaks = (len - 1) / line_len;
+        len + line_breaks * line_ending.as_str().len()
+    }
+
+    /// Encodes `input` breaking lines every `line_len` chars with `line_ending`, without a
+    /// trailing line ending.
+    ///
+    /// A `line_len` of zero disables wrapping, like `base64 -w0` from GNU coreutils.
+    pub fn encode_wrapped(&self, input: &[u8], line_len: usize, line_ending: LineEnding) -> String {
+        if line_len == 0 {
+            return self.encode(input);
+        }
+
+        let len = self.encoded_len_wrapped(input.len(), line_len, line_ending);
+        let mut encoded = String::with_capacity(self.reserved_len(len));
+        self.encode_lines(input, line_len, |line| {
+            if !encoded.is_empty() {
+                encoded.push_str(line_ending.as_str());
+            }
+            encoded.push_str(line);
+        });
+
+        encoded
+    }
+
+    /// Encodes `input` inserting `sep` between every `group` chars, e.g. to display the output
+    /// in space separated blocks. A `group` of zero disables grouping.
+    pub fn encode_grouped(&self, input: &[u8], group: usize, sep: char) -> String {
+        let len = self.encoded_len(input.len());
+        let separators = len.saturating_sub(1).checked_div(group).unwrap_or_default();
+        let mut encoded =
+            String::with_capacity(self.reserved_len(len + separators * sep.len_utf8()));
+
+        self.encode_lines(input, group, |chunk| {
+            if !encoded.is_empty() {
+                encoded.push(sep);
+            }
+            encoded.push_str(chunk);
+        });
+
+        encoded
+    }
+
+    /// Encodes `input` right padded with `fill` up to `width` chars, as expected by fixed width
+    /// fields. Unlike base64 padding, `fill` is not part of the encoding.
+    pub fn encode_fixed_width(
+        &self,
+        input: &[u8],
+        width: usize,
+        fill: char,
+    ) -> Result<String, EncodeError> {
+        let len = self.encoded_len(input.len());
+        if len > width {
+            return Err(EncodeError::ExceedsWidth { len, width });
+        }
+
+        let mut encoded =
+            String::with_capacity(self.reserved_len(len + (width - len) * fill.len_utf8()));
+        self.encode_into(input, &mut encoded);
+        encoded.extend(core::iter::repeat_n(fill, width - len));
+
+        Ok(encoded)
+    }
+
+    /// Encodes `input` wrapping the output every `line_len` chars, calling `sink` once per line
+    /// without building the whole wrapped output. The last line may be shorter than `line_len`.
+    ///
+    /// A `line_len` of zero disables wrapping, delivering the whole encoding as a single line.
+    pub fn encode_lines(&self, input: &[u8], line_len: usize, mut sink: impl FnMut(&str)) {
+        if line_len == 0 {
+            if !input.is_empty() {
+                sink(&self.encode(input));
+            }
+            return;
+        }
+
+        // every 3 * `line_len` input bytes encode into exactly 4 lines, a saturated chunk
+        // length being longer than any input anyway
+        let chunk_len = line_len.saturating_mul(3);
+        let mut encoded = String::with_capacity(self.encoded_len(input.len().min(chunk_len)));
+        for chunk in input.chunks(chunk_len) {
+            encoded.clear();
+            self.encode_into(chunk, &mut encoded);
+
+            // encoded output is ASCII, so splitting at any byte is a valid char boundary
+            for line in encoded.as_bytes().chunks(line_len) {
+                let line = core::str::from_utf8(line).expect("encoded output should be ASCII");
+                sink(line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use crate::{Base64, EncodeError, LineEnding};
+
+    #[test]
+    fn encode_lines_works() {
+        let engine = Base64::standard();
+        let input = b"Many hands make light work, but many more hands make lighter work";
+        let encoded = engine.encode(input);
+
+        for line_len in [4, 7, 19, 76] {
+            let mut lines = Vec::<String>::new();
+            engine.encode_lines(input, line_len, |line| lines.push(line.to_string()));
+
+            let (last, full) = lines.split_last().expect("should emit lines");
+            assert!(full.iter().all(|line| line.len() == line_len));
+            assert!(!last.is_empty() && last.len() <= line_len);
+            assert_eq!(lines.concat(), encoded);
+        }
+    }
+
+    #[test]
+    fn encode_lines_handles_huge_line_len() {
+        let engine = Base64::standard();
+        for line_len in [1 << 31, usize::MAX / 2, usize::MAX] {
+            let mut lines = Vec::<String>::new();
+            engine.encode_lines(b"abc", line_len, |line| lines.push(line.to_string()));
+            assert_eq!(lines, ["YWJj"]);
+
+            assert_eq!(
+                engine.encode_wrapped(b"abc", line_len, LineEnding::Lf),
+                "YWJj"
+            );
+            assert_eq!(engine.encode_grouped(b"abc", line_len, ' '), "YWJj");
+        }
+    }
+
+    #[test]
+    fn encoded_len_wrapped_works() {
+        let engine = Base64::standard();
+        let config = [
+            (0, 76, LineEnding::Lf, 0),
+            (3, 4, LineEnding::Lf, 4),
+            (4, 4, LineEnding::Lf, 9),
+            (57, 76, LineEnding::CrLf, 76),
+            (58, 76, LineEnding::CrLf, 82),
+            (100, 64, LineEnding::Lf, 138),
+            (100, 64, LineEnding::CrLf, 140),
+            (100, 0, LineEnding::CrLf, 136),
+        ];
+
+        for (input_len, line_len, line_ending, expected) in config {
+            let len = engine.encoded_len_wrapped(input_len, line_len, line_ending);
+            assert_eq!(len, expected);
+        }
+    }
+
+    #[test]
+    fn encode_fixed_width_works() {
+        let engine = Base64::standard();
+
+        let encoded = engine.encode_fixed_width(b"abc", 16, ' ');
+        assert_eq!(encoded.as_deref(), Ok("YWJj            "));
+
+        let err = engine.encode_fixed_width(b"abcd", 4, ' ').unwrap_err();
+        assert_eq!(err, EncodeError::ExceedsWidth { len: 8, width: 4 });
+    }
+
+    #[test]
+    fn encode_grouped_works() {
+        let engine = Base64::standard();
+
+        let encoded = engine.encode_grouped(b"light work", 4, ' ');
+        assert_eq!(encoded, "bGln aHQg d29y aw==");
+
+        let encoded = engine.encode_grouped(b"light work", 5, '-');
+        assert_eq!(encoded, "bGlna-HQgd2-9yaw=-=");
+
+        let encoded = engine.encode_grouped(b"light work", 0, ' ');
+        assert_eq!(encoded, engine.encode(b"light work"));
+    }
+
+    #[test]
+    fn encode_wrapped_works() {
+        let engine = Base64::standard();
+        let input = b"Many hands make light work";
+
+        let encoded = engine.encode_wrapped(input, 16, LineEnding::CrLf);
+        assert_eq!(encoded, "TWFueSBoYW5kcyBt\r\nYWtlIGxpZ2h0IHdv\r\ncms=");
+        assert_eq!(
+            encoded.len(),
+            engine.encoded_len_wrapped(input.len(), 16, LineEnding::CrLf)
+        );
+
+        let encoded = engine.encode_wrapped(input, 0, LineEnding::Lf);
+        assert_eq!(encoded, engine.encode(input));
+    }
+}
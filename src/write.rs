@@ -0,0 +1,243 @@
+use std::io::{self, Read, Write};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Base64Engine, EncodeState};
+
+const BUF_LEN: usize = 3 * 1024;
+
+/// Writer that encodes everything written to it into `inner`.
+///
+/// Up to 2 bytes are buffered between writes, so [`EncoderWriter::finish`] must be called once
+/// the message is complete to write them out along with any padding. Every complete window
+/// reaches `inner` as soon as it is written, so `flush` only has to flush `inner` for partial
+/// output to be visible downstream, e.g. between server-sent events.
+#[derive(Debug)]
+pub struct EncoderWriter<W: Write> {
+    inner: W,
+    state: EncodeState,
+    encoded: String,
+}
+
+impl<W: Write> EncoderWriter<W> {
+    pub fn new(engine: Base64Engine, inner: W) -> Self {
+        Self {
+            inner,
+            state: EncodeState::new(engine),
+            encoded: String::new(),
+        }
+    }
+
+    /// Returns the amount of input bytes, 0 to 2, buffered until a window is complete or the
+    /// writer is finished. A stream can be split without padding only when it is zero.
+    pub fn buffered_input(&self) -> usize {
+        self.state.buffered_input()
+    }
+
+    /// Writes the buffered bytes, padded as configured by the engine, and flushes `inner`.
+    ///
+    /// The writer is left ready to encode the next message.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.encoded.clear();
+        self.state.finalize(&mut self.encoded);
+        self.inner.write_all(self.encoded.as_bytes())?;
+        self.inner.flush()
+    }
+
+    /// Clears the writer so it can encode the next message, discarding any buffered bytes that
+    /// were not finished. Bytes already written to `inner` are left untouched.
+    pub fn reset(&mut self) {
+        self.state.reset();
+        self.encoded.clear();
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for EncoderWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoded.clear();
+        self.state.push(buf, &mut self.encoded);
+        self.inner.write_all(self.encoded.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Base64Engine {
+    /// Streams the encoding of everything read from `r` into `w` using a fixed size buffer,
+    /// returning the amount of encoded bytes written.
+    pub fn encode_pipe(&self, r: &mut impl Read, w: &mut impl Write) -> io::Result<u64> {
+        let mut buf = [0u8; BUF_LEN];
+        let mut encoded = String::with_capacity(self.encoded_len(BUF_LEN));
+        let mut written = 0u64;
+        let mut filled = 0;
+
+        loop {
+            let read = match r.read(&mut buf[filled..]) {
+                Ok(read) => read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            filled += read;
+
+            // the buffer is a multiple of 3, so only the last chunk before EOF may need padding
+            let eof = read == 0;
+            if filled > 0 && (eof || filled == BUF_LEN) {
+                encoded.clear();
+                self.encode_into(&buf[..filled], &mut encoded);
+                w.write_all(encoded.as_bytes())?;
+                written += encoded.len() as u64;
+                filled = 0;
+            }
+
+            if eof {
+                return Ok(written);
+            }
+        }
+    }
+
+    /// Decodes `input` into `w` through a fixed size buffer, returning the amount of decoded
+    /// bytes written.
+    ///
+    /// Decoding errors are reported as [`io::ErrorKind::InvalidData`], in which case bytes
+    /// decoded before the error may already have been written to `w`.
+    pub fn decode_to_writer(&self, input: &[u8], w: &mut impl Write) -> io::Result<usize> {
+        let mut buf = Vec::with_capacity(BUF_LEN);
+        let mut written = 0;
+        let mut result = Ok(());
+
+        let decoded = self.decode_each(input, |window| {
+            // once `w` fails there is no point in writing anything else
+            if result.is_err() {
+                return;
+            }
+
+            buf.extend_from_slice(window);
+            if buf.len() >= BUF_LEN {
+                result = w.write_all(&buf);
+                written += buf.len();
+                buf.clear();
+            }
+        });
+
+        result?;
+        decoded?;
+        w.write_all(&buf)?;
+
+        Ok(written + buf.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::Base64;
+
+    #[test]
+    fn encode_pipe_works() {
+        let engine = Base64::standard();
+        let input = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+
+        let mut reader = Cursor::new(&input);
+        let mut output = Vec::<u8>::new();
+        let written = engine
+            .encode_pipe(&mut reader, &mut output)
+            .expect("should pipe encoding");
+
+        let expected = engine.encode(&input);
+        assert_eq!(written, expected.len() as u64);
+        assert_eq!(output, expected.as_bytes());
+    }
+
+    #[test]
+    fn decode_to_writer_works() {
+        let engine = Base64::standard();
+        let input = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        let encoded = engine.encode(&input);
+
+        let mut output = Vec::<u8>::new();
+        let written = engine
+            .decode_to_writer(encoded.as_bytes(), &mut output)
+            .expect("should decode into writer");
+        assert_eq!(written, input.len());
+        assert_eq!(Ok(output), engine.decode(&encoded));
+
+        let err = engine
+            .decode_to_writer(b"bG*n", &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn encoder_writer_reset_works() {
+        let engine = Base64::standard();
+        let mut writer = EncoderWriter::new(engine.clone(), Vec::<u8>::new());
+        let messages: [&[u8]; 3] = [b"light w", b"light wo", b"light wor"];
+
+        for message in messages {
+            writer.write_all(b"xy").expect("should write");
+            writer.reset();
+
+            for piece in message.chunks(4) {
+                writer.write_all(piece).expect("should write");
+            }
+            writer.finish().expect("should finish");
+
+            let encoded = core::mem::take(writer.get_mut());
+            assert_eq!(encoded, engine.encode(message).as_bytes());
+        }
+    }
+
+    #[test]
+    fn encoder_writer_buffered_input_works() {
+        let mut writer = EncoderWriter::new(Base64::standard(), Vec::<u8>::new());
+        assert_eq!(writer.buffered_input(), 0);
+
+        writer.write_all(b"light w").expect("should write");
+        assert_eq!(writer.buffered_input(), 1);
+        assert_eq!(writer.get_ref(), b"bGlnaHQg");
+
+        writer.finish().expect("should finish");
+        assert_eq!(writer.buffered_input(), 0);
+        assert_eq!(writer.get_ref(), b"bGlnaHQgdw==");
+    }
+
+    #[test]
+    fn encoder_writer_flush_forwards_complete_windows() {
+        let mut writer = EncoderWriter::new(Base64::standard(), Vec::<u8>::new());
+        let pieces: [(&[u8], usize); 4] = [(b"li", 0), (b"g", 4), (b"ht w", 8), (b"or", 12)];
+
+        for (piece, written) in pieces {
+            writer.write_all(piece).expect("should write");
+            writer.flush().expect("should flush");
+            assert_eq!(writer.get_ref().len(), written);
+        }
+        assert_eq!(writer.get_ref(), b"bGlnaHQgd29y");
+        assert_eq!(writer.buffered_input(), 0);
+
+        writer.write_all(b"k").expect("should write");
+        writer.flush().expect("should flush");
+        assert_eq!(writer.get_ref(), b"bGlnaHQgd29y");
+        writer.finish().expect("should finish");
+        assert_eq!(writer.get_ref(), b"bGlnaHQgd29yaw==");
+    }
+}